@@ -0,0 +1,203 @@
+use crate::err::RpErr;
+
+/// 读取剪切板文本。
+#[cfg(windows)]
+pub(crate) fn get_text() -> Result<String, RpErr> {
+    clipboard_win::get_clipboard_string().map_err(|err| RpErr::ReadClipboardTextErr(err.to_string()))
+}
+
+/// 写入剪切板文本。
+#[cfg(windows)]
+pub(crate) fn set_text(text: &str) -> Result<(), RpErr> {
+    clipboard_win::set_clipboard_string(text).map_err(|err| RpErr::WriteToClipboardErr(err.to_string()))
+}
+
+/// 读取剪切板文本。
+#[cfg(target_os = "linux")]
+pub(crate) fn get_text() -> Result<String, RpErr> {
+    linux::ClipCmds::detect(|name| std::env::var_os(name))
+        .and_then(|cmds| cmds.paste())
+        .map_err(RpErr::ReadClipboardTextErr)
+}
+
+/// 写入剪切板文本。
+#[cfg(target_os = "linux")]
+pub(crate) fn set_text(text: &str) -> Result<(), RpErr> {
+    linux::ClipCmds::detect(|name| std::env::var_os(name))
+        .and_then(|cmds| cmds.copy(text))
+        .map_err(RpErr::WriteToClipboardErr)
+}
+
+/// Linux下没有统一的剪切板接口，通过`wl-clipboard`、`xclip`或`xsel`命令读写。
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::ffi::OsString;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::process::{Command, Stdio};
+
+    /// 指定剪切板后端的环境变量，可选值为`wayland`、`xclip`、`xsel`，未指定时自动探测。
+    pub(super) const CLIP_BACKEND_ENV: &str = "RP_CLIP_BACKEND";
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    pub(super) enum ClipBackend {
+        /// `wl-paste`/`wl-copy`，需要Wayland会话。
+        Wayland,
+        Xclip,
+        Xsel,
+    }
+
+    /// 命令及其参数。
+    type CmdLine = (&'static str, &'static [&'static str]);
+
+    impl ClipBackend {
+        /// 读取和写入剪切板的命令及参数。
+        fn cmds(&self) -> (CmdLine, CmdLine) {
+            match self {
+                ClipBackend::Wayland => (("wl-paste", &["--no-newline"]), ("wl-copy", &[])),
+                ClipBackend::Xclip => {
+                    (("xclip", &["-selection", "clipboard", "-out"]), ("xclip", &["-selection", "clipboard", "-in"]))
+                }
+                ClipBackend::Xsel => (("xsel", &["--clipboard", "--output"]), ("xsel", &["--clipboard", "--input"])),
+            }
+        }
+    }
+
+    /// 选定的后端及其命令在`PATH`中的完整路径。
+    #[derive(Debug)]
+    pub(super) struct ClipCmds {
+        pub(super) backend: ClipBackend,
+        paste: PathBuf,
+        copy: PathBuf,
+    }
+
+    impl ClipCmds {
+        /// 优先使用环境变量指定的后端，否则在Wayland会话中优先使用`wl-clipboard`，其次依次尝试`xclip`和`xsel`。
+        pub(super) fn detect(var: impl Fn(&str) -> Option<OsString>) -> Result<Self, String> {
+            let path = var("PATH").unwrap_or_default();
+            let find_program =
+                |program: &str| std::env::split_paths(&path).map(|dir| dir.join(program)).find(|file| file.is_file());
+            let find = |backend: ClipBackend| {
+                let ((paste, _), (copy, _)) = backend.cmds();
+                Some(ClipCmds { backend, paste: find_program(paste)?, copy: find_program(copy)? })
+            };
+            if let Some(name) = var(CLIP_BACKEND_ENV).filter(|name| !name.is_empty()) {
+                let name = name.to_string_lossy();
+                let backend = match name.to_ascii_lowercase().as_str() {
+                    "wayland" => ClipBackend::Wayland,
+                    "xclip" => ClipBackend::Xclip,
+                    "xsel" => ClipBackend::Xsel,
+                    _ => return Err(format!("unknown {CLIP_BACKEND_ENV} {name:?}, expect wayland, xclip or xsel")),
+                };
+                return find(backend).ok_or_else(|| format!("commands of clipboard backend {name:?} not found"));
+            }
+            let wayland = var("WAYLAND_DISPLAY").is_some_and(|display| !display.is_empty());
+            wayland
+                .then(|| find(ClipBackend::Wayland))
+                .flatten()
+                .or_else(|| find(ClipBackend::Xclip))
+                .or_else(|| find(ClipBackend::Xsel))
+                .ok_or_else(|| "no clipboard command found, please install wl-clipboard, xclip or xsel".to_string())
+        }
+
+        pub(super) fn paste(&self) -> Result<String, String> {
+            let ((_, args), _) = self.backend.cmds();
+            let output = Command::new(&self.paste)
+                .args(args)
+                .stdin(Stdio::null())
+                .output()
+                .map_err(|err| format!("run {:?} error: {err}", self.paste))?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("{:?} failed: {}, {}", self.paste, output.status, stderr.trim()));
+            }
+            String::from_utf8(output.stdout).map_err(|err| err.to_string())
+        }
+
+        pub(super) fn copy(&self, text: &str) -> Result<(), String> {
+            let (_, (_, args)) = self.backend.cmds();
+            // 部分命令会在后台常驻以提供剪切板内容，因此不捕获其输出，避免等待输出关闭
+            let mut child = Command::new(&self.copy)
+                .args(args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|err| format!("run {:?} error: {err}", self.copy))?;
+            let written = child.stdin.take().map_or(Ok(()), |mut stdin| stdin.write_all(text.as_bytes()));
+            let status = child.wait().map_err(|err| format!("wait {:?} error: {err}", self.copy))?;
+            written.map_err(|err| format!("write to {:?} error: {err}", self.copy))?;
+            if status.success() { Ok(()) } else { Err(format!("{:?} failed: {status}", self.copy)) }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod tests {
+    use super::linux::*;
+    use std::ffi::OsString;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+
+    /// 在临时目录中创建替代剪切板命令的脚本，读写同一个文件。
+    fn stand_in(name: &str, programs: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rp_clip_{}_{name}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let store = dir.join("clipboard.txt");
+        for program in programs {
+            let script = if program.contains("paste") || *program == "xclip" || *program == "xsel" {
+                // xclip和xsel通过参数区分读写
+                format!(
+                    "#!/bin/sh\ncase \"$*\" in *-out*|*--output*|'--no-newline') cat '{0}';; *) cat > '{0}';; esac\n",
+                    store.display()
+                )
+            } else {
+                format!("#!/bin/sh\ncat > '{}'\n", store.display())
+            };
+            let file = dir.join(program);
+            std::fs::write(&file, script).unwrap();
+            std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        dir
+    }
+
+    fn env<'a>(vars: &'a [(&'a str, &'a Path)]) -> impl Fn(&str) -> Option<OsString> + 'a {
+        move |name| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.as_os_str().to_os_string())
+    }
+
+    #[test]
+    fn test_detect() {
+        let wayland = stand_in("detect_wayland", &["wl-copy", "wl-paste"]);
+        let xsel = stand_in("detect_xsel", &["xsel"]);
+        let path = std::env::join_paths([&wayland, &xsel]).unwrap();
+        let path = Path::new(&path);
+        let display = Path::new("wayland-0");
+
+        let cmds = ClipCmds::detect(env(&[("PATH", path), ("WAYLAND_DISPLAY", display)])).unwrap();
+        assert_eq!(ClipBackend::Wayland, cmds.backend);
+        let cmds = ClipCmds::detect(env(&[("PATH", path)])).unwrap();
+        assert_eq!(ClipBackend::Xsel, cmds.backend);
+        let backend = Path::new("XSEL");
+        let cmds = ClipCmds::detect(env(&[("PATH", path), ("WAYLAND_DISPLAY", display), (CLIP_BACKEND_ENV, backend)]));
+        assert_eq!(ClipBackend::Xsel, cmds.unwrap().backend);
+
+        let backend = Path::new("xclip");
+        assert!(ClipCmds::detect(env(&[("PATH", path), (CLIP_BACKEND_ENV, backend)])).is_err());
+        let backend = Path::new("pbcopy");
+        assert!(ClipCmds::detect(env(&[("PATH", path), (CLIP_BACKEND_ENV, backend)])).is_err());
+        assert!(ClipCmds::detect(env(&[("PATH", &wayland)])).is_err());
+    }
+
+    #[test]
+    fn test_copy_paste() {
+        for (name, programs) in [("wayland", &["wl-copy", "wl-paste"][..]), ("xclip", &["xclip"]), ("xsel", &["xsel"])]
+        {
+            let dir = stand_in(name, programs);
+            let backend = Path::new(name);
+            let cmds = ClipCmds::detect(env(&[("PATH", &dir), (CLIP_BACKEND_ENV, backend)])).unwrap();
+            cmds.copy("a\nb\n").unwrap();
+            assert_eq!("a\nb\n", cmds.paste().unwrap(), "{name}");
+        }
+    }
+}
@@ -4,6 +4,10 @@ use thiserror::Error;
 
 #[derive(Error, Clone, Debug, Eq, PartialEq, CmdHelp)]
 pub enum RpErr {
+    ///  0      下游已关闭标准输出（例如`rp ... | head`），停止输出并正常退出。
+    #[error("[StdOutClosed:0] Stdout is closed")]
+    StdOutClosed,
+
     ///  1      解析配置Token失败。
     #[error("[ParseTokenErr:1] Parse token err: {0}")]
     ParseTokenErr(String),
@@ -61,6 +65,62 @@ pub enum RpErr {
         "[InvalidNonNegativeIntArg:14] Positive integer or zero is required by argument `{arg}` of cmd `{cmd}`, but it is {arg_value:?}"
     )]
     InvalidNonNegativeIntArg { cmd: &'static str, arg: &'static str, arg_value: String },
+
+    /// 15      需要缓存全部数据的操作无法用于永不结束的输入。
+    #[error("[BufferedOpOnFollowErr:15] Cmd `{cmd}` needs all items before output, but the input never ends")]
+    BufferedOpOnFollowErr { cmd: &'static str },
+
+    /// 16      启动外部命令失败。
+    #[error("[SpawnCmdErr:16] Spawn cmd {cmd:?} error: {err}")]
+    SpawnCmdErr { cmd: String, err: String },
+
+    /// 17      从外部命令读取数据失败。
+    #[error("[ReadFromCmdErr:17] Read output of cmd {cmd:?} error: {err}")]
+    ReadFromCmdErr { cmd: String, err: String },
+
+    /// 18      `:exec`的外部命令执行失败。
+    #[error("[CmdExitErr:18] Cmd {cmd:?} failed: {status}")]
+    CmdExitErr { cmd: String, status: String },
+
+    /// 19      读取目录失败。
+    #[error("[ReadDirErr:19] Read dir {dir:?} error: {err}")]
+    ReadDirErr { dir: String, err: String },
+
+    /// 20      拼接的数据源数量不一致。
+    #[error("[ZipLengthErr:20] Cmd `{cmd}` got items of different counts from input and file {file:?}")]
+    ZipLengthErr { cmd: &'static str, file: String },
+
+    /// 21      Unix域套接字读写失败。
+    #[error("[SocketErr:21] Socket {path:?} error: {err}")]
+    SocketErr { path: String, err: String },
+
+    /// 22      数据解析为JSON失败。
+    #[error("[ParseJsonErr:22] Parse item {item:?} as JSON error: {err}")]
+    ParseJsonErr { item: String, err: String },
+
+    /// 23      写入标准输出失败。
+    #[error("[WriteToStdOutErr:23] Write to stdout error: {0}")]
+    WriteToStdOutErr(String),
+
+    /// 24      `:to inplace`只能用于非跟踪模式的文件输入。
+    #[error("[InPlaceInputErr:24] Cmd `:to inplace` requires input `:file` without `follow`")]
+    InPlaceInputErr,
+
+    /// 25      写入数据到外部命令失败。
+    #[error("[WriteToCmdErr:25] Write to stdin of cmd {cmd:?} error: {err}")]
+    WriteToCmdErr { cmd: String, err: String },
+
+    /// 26      `:to split`从数据中提取的键无法作为文件名。
+    #[error("[SplitKeyErr:26] Key {key:?} extracted from item {item:?} can not be used as file name")]
+    SplitKeyErr { item: String, key: String },
+
+    /// 27      数据编码为CSV记录失败。
+    #[error("[EncodeCsvErr:27] Encode item {item:?} as CSV error: {err}")]
+    EncodeCsvErr { item: String, err: String },
+
+    /// 28      `:to exec`的外部命令执行失败。
+    #[error("[OutputCmdExitErr:28] Output cmd {cmd:?} failed: {status}")]
+    OutputCmdExitErr { cmd: String, status: String },
 }
 
 impl Termination for RpErr {
@@ -70,15 +130,27 @@ impl Termination for RpErr {
     }
 }
 
+/// 直接退出进程。直接退出时不会执行析构，需要先手动写入压缩流的结尾并删除未完成的临时文件。
+fn exit_cleanly(code: i32) -> ! {
+    crate::output::compress::finish_open_files();
+    crate::output::atomic::remove_temp_files();
+    std::process::exit(code)
+}
+
 impl RpErr {
     pub fn termination(self) -> ! {
         let exit_code = self.exit_code();
-        self.report();
-        std::process::exit(exit_code as i32);
+        // 下游已关闭时无需输出和报告，其他错误先输出已缓冲的数据再报告，此时下游已关闭也不影响退出码
+        if self != RpErr::StdOutClosed {
+            let _ = crate::stdout::flush();
+            self.report();
+        }
+        exit_cleanly(exit_code as i32)
     }
 
     fn exit_code(&self) -> u8 {
         match self {
+            RpErr::StdOutClosed => 0,
             RpErr::ParseTokenErr(_) => 1,
             RpErr::ArgParseErr { .. } => 2,
             RpErr::MissingArg { .. } => 3,
@@ -93,6 +165,20 @@ impl RpErr {
             RpErr::ParseRegexErr { .. } => 12,
             RpErr::ParseNumErr { .. } => 13,
             RpErr::InvalidNonNegativeIntArg { .. } => 14,
+            RpErr::BufferedOpOnFollowErr { .. } => 15,
+            RpErr::SpawnCmdErr { .. } => 16,
+            RpErr::ReadFromCmdErr { .. } => 17,
+            RpErr::CmdExitErr { .. } => 18,
+            RpErr::ReadDirErr { .. } => 19,
+            RpErr::ZipLengthErr { .. } => 20,
+            RpErr::SocketErr { .. } => 21,
+            RpErr::ParseJsonErr { .. } => 22,
+            RpErr::WriteToStdOutErr(_) => 23,
+            RpErr::InPlaceInputErr => 24,
+            RpErr::WriteToCmdErr { .. } => 25,
+            RpErr::SplitKeyErr { .. } => 26,
+            RpErr::EncodeCsvErr { .. } => 27,
+            RpErr::OutputCmdExitErr { .. } => 28,
         }
     }
 }
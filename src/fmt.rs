@@ -48,6 +48,12 @@ impl From<Float> for FmtArg {
     }
 }
 
+impl std::fmt::Display for FmtArg {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.fmt_display(f)
+    }
+}
+
 impl FormatArgument for FmtArg {
     fn supports_format(&self, specifier: &Specifier) -> bool {
         match self {
@@ -118,6 +124,16 @@ pub(crate) fn fmt_args(fmt: &str, args: &[(&str, FmtArg)]) -> Result<String, RpE
     }
 }
 
+/// 同时支持以`{0}`或`{}`引用的位置参数和以名称引用的命名参数。
+pub(crate) fn fmt_positional_args(fmt: &str, positional: &[FmtArg], named: &[(&str, FmtArg)]) -> Result<String, RpErr> {
+    match ParsedFormat::parse(fmt, positional, &named) {
+        Ok(string) => Ok(format!("{}", string)),
+        Err(err_pos) => {
+            Err(RpErr::FormatStringErr { fmt: fmt.to_owned(), value: format!("{positional:?} {named:?}"), err_pos })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +150,14 @@ mod tests {
             fmt_args("{{{name}}}", &[("name", FmtArg::from("Jack")), ("age", FmtArg::from(12))])
         );
     }
+
+    #[test]
+    fn test_fmt_positional_args() {
+        let positional = [FmtArg::from("a"), FmtArg::from(80)];
+        assert_eq!(
+            Ok("a:80 a 80 x".to_string()),
+            fmt_positional_args("{0}:{1} {} {} {name}", &positional, &[("name", FmtArg::from("x"))])
+        );
+        assert!(fmt_positional_args("{2}", &positional, &[]).is_err());
+    }
 }
@@ -0,0 +1,146 @@
+use crate::err::RpErr;
+use crate::fmt::{FmtArg, fmt_positional_args};
+use itertools::Itertools;
+
+/// 一组候选值，可以通过`<name>=`前缀命名，以便在格式化字符串中按名称引用。
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct ValueList {
+    name: Option<String>,
+    values: Vec<String>,
+}
+
+impl ValueList {
+    pub(crate) fn new(name: Option<String>, values: Vec<String>) -> ValueList {
+        ValueList { name, values }
+    }
+
+    /// 解析`[<name>=]<value>[,<value>][...]`，仅当`=`之前为合法标识符时视为名称。
+    pub(crate) fn parse(text: &str) -> ValueList {
+        let (name, values) = match text.split_once('=') {
+            Some((name, values)) if is_identifier(name) => (Some(name.to_string()), values),
+            _ => (None, text),
+        };
+        ValueList::new(name, split_values(values))
+    }
+}
+
+/// 按照`,`拆分候选值。
+pub(crate) fn split_values(text: &str) -> Vec<String> {
+    text.split(',').map(String::from).collect()
+}
+
+/// 检查排列或组合的元素个数，必须为正整数。
+pub(crate) fn check_k(cmd: &'static str, k: usize) -> Result<usize, RpErr> {
+    if k > 0 {
+        Ok(k)
+    } else {
+        Err(RpErr::ArgParseErr {
+            cmd,
+            arg: "k",
+            arg_value: k.to_string(),
+            error: "k must be a positive integer".to_string(),
+        })
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// 按需生成多组候选值的笛卡尔积。
+pub(in crate::input) fn product(lists: Vec<ValueList>, fmt: Option<String>) -> Box<dyn Iterator<Item = String>> {
+    let names = lists.iter().map(|list| list.name.clone()).collect::<Vec<_>>();
+    let iter = lists.into_iter().map(|list| list.values.into_iter()).multi_cartesian_product();
+    Box::new(iter.map(move |values| format_values(values, &names, fmt.as_deref())))
+}
+
+/// 按需生成候选值中`k`个元素的排列。
+pub(in crate::input) fn permutations(
+    k: usize, values: Vec<String>, fmt: Option<String>,
+) -> Box<dyn Iterator<Item = String>> {
+    Box::new(values.into_iter().permutations(k).map(move |values| format_values(values, &[], fmt.as_deref())))
+}
+
+/// 按需生成候选值中`k`个元素的组合，组合内元素保持原有顺序。
+pub(in crate::input) fn combinations(
+    k: usize, values: Vec<String>, fmt: Option<String>,
+) -> Box<dyn Iterator<Item = String>> {
+    Box::new(values.into_iter().combinations(k).map(move |values| format_values(values, &[], fmt.as_deref())))
+}
+
+/// 未指定格式化字符串时以空格连接各个值。
+fn format_values(values: Vec<String>, names: &[Option<String>], fmt: Option<&str>) -> String {
+    match fmt {
+        Some(fmt) => {
+            let named = names
+                .iter()
+                .zip(&values)
+                .filter_map(|(name, value)| name.as_deref().map(|name| (name, FmtArg::from(value))))
+                .collect::<Vec<_>>();
+            let positional = values.into_iter().map(FmtArg::from).collect::<Vec<_>>();
+            match fmt_positional_args(fmt, &positional, &named) {
+                Ok(string) => string,
+                Err(err) => err.termination(),
+            }
+        }
+        None => values.join(" "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_value_list() {
+        assert_eq!(ValueList::new(None, strings(&["a", "b"])), ValueList::parse("a,b"));
+        assert_eq!(ValueList::new(Some("host".to_string()), strings(&["a", ""])), ValueList::parse("host=a,"));
+        assert_eq!(ValueList::new(Some("k".to_string()), strings(&["v=1"])), ValueList::parse("k=v=1"));
+        assert_eq!(ValueList::new(None, strings(&["1=a", "b"])), ValueList::parse("1=a,b"));
+        assert_eq!(ValueList::new(None, strings(&["=a"])), ValueList::parse("=a"));
+    }
+
+    #[test]
+    fn test_product() {
+        let lists = vec![ValueList::parse("a,b"), ValueList::parse("1,2,3")];
+        assert_eq!(strings(&["a 1", "a 2", "a 3", "b 1", "b 2", "b 3"]), product(lists, None).collect::<Vec<_>>());
+
+        let lists = vec![ValueList::parse("host=x,y"), ValueList::parse("port=80,443")];
+        assert_eq!(
+            strings(&["x:80", "x:443", "y:80", "y:443"]),
+            product(lists, Some("{host}:{1}".to_string())).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_product_lazy() {
+        let lists = (0..10).map(|_| ValueList::parse("0,1,2,3,4,5,6,7,8,9")).collect::<Vec<_>>();
+        assert_eq!(
+            strings(&["0000000000", "0000000001", "0000000002"]),
+            product(lists, Some("{}{}{}{}{}{}{}{}{}{}".to_string())).take(3).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(
+            strings(&["a b", "a c", "b a", "b c", "c a", "c b"]),
+            permutations(2, strings(&["a", "b", "c"]), None).collect::<Vec<_>>()
+        );
+        assert!(permutations(4, strings(&["a", "b", "c"]), None).next().is_none());
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(
+            strings(&["a-b", "a-c", "b-c"]),
+            combinations(2, strings(&["a", "b", "c"]), Some("{0}-{1}".to_string())).collect::<Vec<_>>()
+        );
+        assert_eq!(strings(&["a b c"]), combinations(3, strings(&["a", "b", "c"]), None).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use crate::pipe::{ItemFields, ItemMeta, MetaCell};
+use ::csv::{Reader, ReaderBuilder, StringRecord, WriterBuilder};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::rc::Rc;
+
+/// 未指定时使用的字段分隔符。
+pub(crate) const DEFAULT_CSV_DELIMITER: u8 = b',';
+
+/// 从标准输入读取时使用的来源文件名称。
+const STDIN_NAME: &str = "-";
+
+/// 解析字段分隔符，必须为单个ASCII字符，且不能是引号或换行符。
+pub(crate) fn parse_delimiter(value: &str) -> Result<u8, RpErr> {
+    match value.as_bytes() {
+        [b] if b.is_ascii() && !matches!(b, b'"' | b'\r' | b'\n') => Ok(*b),
+        _ => Err(RpErr::ArgParseErr {
+            cmd: ":csv",
+            arg: "delimiter",
+            arg_value: value.to_string(),
+            error: "delimiter must be a single ASCII char other than quote or line break".to_string(),
+        }),
+    }
+}
+
+/// 按照RFC 4180逐条读取CSV记录，每条记录重新以相同的分隔符序列化为一个数据，
+/// 记录的各个字段通过来源信息提供给下游。
+pub(in crate::input) struct CsvRecords {
+    file: Rc<String>,
+    reader: Reader<NewlineRecorder>,
+    /// 已读取但尚未越过的换行符的字节偏移。
+    newlines: Rc<RefCell<VecDeque<u64>>>,
+    /// 已越过的换行符数量。
+    lines: usize,
+    delimiter: u8,
+    header: bool,
+    names: Option<Rc<Vec<String>>>,
+    record: StringRecord,
+    meta: MetaCell,
+    configs: &'static [Config],
+}
+
+impl CsvRecords {
+    /// `file`为`None`时从标准输入读取，`header`为`true`时第一条记录作为字段名称，不作为数据输出。
+    pub(in crate::input) fn open(
+        file: Option<String>, delimiter: u8, header: bool, configs: &'static [Config],
+    ) -> Result<Self, RpErr> {
+        let (file, read): (String, Box<dyn Read>) = match file {
+            Some(file) => {
+                let fin =
+                    File::open(&file).map_err(|err| RpErr::OpenFileErr { file: file.clone(), err: err.to_string() })?;
+                (file, Box::new(fin))
+            }
+            None => (STDIN_NAME.to_string(), Box::new(io::stdin())),
+        };
+        Ok(CsvRecords::new(file, read, delimiter, header, configs))
+    }
+
+    fn new(file: String, read: Box<dyn Read>, delimiter: u8, header: bool, configs: &'static [Config]) -> Self {
+        let newlines = Rc::new(RefCell::new(VecDeque::new()));
+        let recorder = NewlineRecorder { read, offset: 0, newlines: newlines.clone() };
+        CsvRecords {
+            file: Rc::new(file),
+            reader: ReaderBuilder::new().delimiter(delimiter).has_headers(false).flexible(true).from_reader(recorder),
+            newlines,
+            lines: 0,
+            delimiter,
+            header,
+            names: None,
+            record: StringRecord::new(),
+            meta: MetaCell::default(),
+            configs,
+        }
+    }
+
+    pub(in crate::input) fn meta(&self) -> MetaCell {
+        self.meta.clone()
+    }
+
+    /// 读取下一条记录，返回记录开始的行号。
+    fn read_record(&mut self) -> Option<usize> {
+        loop {
+            match self.reader.read_record(&mut self.record) {
+                Ok(true) => {
+                    // 记录以CRLF结尾时，`csv`给出的下一条记录的位置在`\n`上，且行号未计入此换行符，
+                    // 因此根据字节偏移自行统计行号，位于记录开始位置的换行符也计入
+                    let start = self.record.position().map_or(0, |pos| pos.byte());
+                    let mut newlines = self.newlines.borrow_mut();
+                    while newlines.front().is_some_and(|offset| *offset <= start) {
+                        newlines.pop_front();
+                        self.lines += 1;
+                    }
+                    return Some(self.lines + 1);
+                }
+                Ok(false) => return None,
+                Err(err) => {
+                    if !skip_err(self.configs) {
+                        let line_no = err.position().map_or(0, |pos| pos.line() as usize);
+                        RpErr::ReadFromFileErr { file: (*self.file).clone(), line_no, err: err.to_string() }
+                            .termination()
+                    }
+                }
+            }
+        }
+    }
+
+    fn serialize(&mut self) -> String {
+        let mut writer = WriterBuilder::new().delimiter(self.delimiter).from_writer(vec![]);
+        let mut bytes = match writer.write_record(&self.record).map_err(|err| err.to_string()) {
+            Ok(()) => writer.into_inner().map_err(|err| err.to_string()),
+            Err(err) => Err(err),
+        }
+        .unwrap_or_else(|err| RpErr::ReadFromFileErr { file: (*self.file).clone(), line_no: 0, err }.termination());
+        while bytes.last().is_some_and(|b| matches!(b, b'\r' | b'\n')) {
+            bytes.pop();
+        }
+        // 记录的各个字段均为合法UTF-8，序列化后仍为合法UTF-8
+        String::from_utf8(bytes).unwrap_or_default()
+    }
+}
+
+impl Iterator for CsvRecords {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.header && self.names.is_none() {
+            self.read_record()?;
+            self.names = Some(Rc::new(self.record.iter().map(String::from).collect()));
+        }
+        let line = self.read_record()?;
+        let values = self.record.iter().map(String::from).collect();
+        *self.meta.borrow_mut() = Some(ItemMeta {
+            file: self.file.clone(),
+            line,
+            fields: Some(ItemFields { names: self.names.clone(), values }),
+        });
+        Some(self.serialize())
+    }
+}
+
+/// 记录读取到的所有换行符的字节偏移。
+struct NewlineRecorder {
+    read: Box<dyn Read>,
+    offset: u64,
+    newlines: Rc<RefCell<VecDeque<u64>>>,
+}
+
+impl Read for NewlineRecorder {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.read.read(buf)?;
+        let mut newlines = self.newlines.borrow_mut();
+        newlines.extend(
+            buf[..read].iter().enumerate().filter(|(_, b)| **b == b'\n').map(|(idx, _)| self.offset + idx as u64),
+        );
+        self.offset += read as u64;
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(content: &'static str, delimiter: u8, header: bool) -> Vec<(String, ItemMeta)> {
+        let records = CsvRecords::new("t.csv".to_string(), Box::new(content.as_bytes()), delimiter, header, &[]);
+        let meta = records.meta();
+        records.map(|item| (item, meta.borrow().clone().unwrap())).collect()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_delimiter() {
+        assert_eq!(Ok(b','), parse_delimiter(","));
+        assert_eq!(Ok(b'\t'), parse_delimiter("\t"));
+        assert!(parse_delimiter("ab").is_err());
+        assert!(parse_delimiter("\"").is_err());
+        assert!(parse_delimiter("，").is_err());
+    }
+
+    #[test]
+    fn test_csv_records() {
+        let records = records("a,\"b,c\"\n\"x\"\"y\",\"multi\nline\"\n\n3\n", b',', false);
+        assert_eq!(
+            vec!["a,\"b,c\"", "\"x\"\"y\",\"multi\nline\"", "3"],
+            records.iter().map(|(item, _)| item.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![1, 2, 5], records.iter().map(|(_, meta)| meta.line).collect::<Vec<_>>());
+        assert_eq!(strings(&["x\"y", "multi\nline"]), records[1].1.fields.as_ref().unwrap().values);
+        assert_eq!(None, records[1].1.fields.as_ref().unwrap().names);
+    }
+
+    #[test]
+    fn test_csv_records_with_header() {
+        let records = records("name;age\r\nJack;12\r\n\"Rose; Jr\";9\r\n", b';', true);
+        assert_eq!(
+            vec!["Jack;12", "\"Rose; Jr\";9"],
+            records.iter().map(|(item, _)| item.as_str()).collect::<Vec<_>>()
+        );
+        let fields = records[1].1.fields.as_ref().unwrap();
+        assert_eq!(Some(Rc::new(strings(&["name", "age"]))), fields.names);
+        assert_eq!(strings(&["Rose; Jr", "9"]), fields.values);
+        assert_eq!(3, records[1].1.line);
+    }
+}
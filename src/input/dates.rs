@@ -0,0 +1,177 @@
+use crate::err::RpErr;
+use crate::fmt::{FmtArg, fmt_args};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Months, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+
+/// 日期时间的步长。
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum DateStep {
+    /// 固定时长，单位为秒。
+    Seconds(i64),
+    /// 自然月，月末日期按照目标月份的最后一天处理。
+    Months(u32),
+}
+
+impl DateStep {
+    fn is_whole_days(&self) -> bool {
+        match self {
+            DateStep::Seconds(seconds) => seconds % 86400 == 0,
+            DateStep::Months(_) => true,
+        }
+    }
+}
+
+/// 解析`YYYY-MM-DD`、`YYYY-MM-DDTHH:MM`或`YYYY-MM-DDTHH:MM:SS`格式的日期时间，`T`也可以使用空格代替。
+pub(crate) fn parse_datetime(arg: &'static str, value: &str) -> Result<NaiveDateTime, RpErr> {
+    let normalized = value.replacen(' ', "T", 1);
+    NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(&normalized, "%Y-%m-%dT%H:%M"))
+        .or_else(|_| NaiveDate::parse_from_str(&normalized, "%Y-%m-%d").map(|date| date.and_time(NaiveTime::MIN)))
+        .map_err(|err| RpErr::ArgParseErr { cmd: ":dates", arg, arg_value: value.to_string(), error: err.to_string() })
+}
+
+/// 检查strftime格式是否有效。
+pub(crate) fn check_pattern(pattern: &str) -> Result<(), RpErr> {
+    StrftimeItems::new(pattern).parse().map(|_| ()).map_err(|err| RpErr::ArgParseErr {
+        cmd: ":dates",
+        arg: "pattern",
+        arg_value: pattern.to_string(),
+        error: err.to_string(),
+    })
+}
+
+/// 按照步长生成闭区间内的日期时间，第`n`个值由起始值直接加上`n`倍步长得到，避免月末日期的累积偏移。
+pub(in crate::input) struct DatesIter {
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    step: DateStep,
+    index: u32,
+    items: Vec<Item<'static>>,
+    fmt: Option<String>,
+}
+
+impl DatesIter {
+    pub(in crate::input) fn new(
+        start: NaiveDateTime, end: NaiveDateTime, step: DateStep, pattern: Option<String>, fmt: Option<String>,
+    ) -> Result<Self, RpErr> {
+        let pattern = pattern.unwrap_or_else(|| {
+            if step.is_whole_days() && start.time() == NaiveTime::MIN { "%Y-%m-%d" } else { "%Y-%m-%d %H:%M:%S" }
+                .to_string()
+        });
+        let items = StrftimeItems::new(&pattern).parse_to_owned().map_err(|err| RpErr::ArgParseErr {
+            cmd: ":dates",
+            arg: "pattern",
+            arg_value: pattern.clone(),
+            error: err.to_string(),
+        })?;
+        Ok(DatesIter { start, end, step, index: 0, items, fmt })
+    }
+
+    fn nth_datetime(&self) -> Option<NaiveDateTime> {
+        match self.step {
+            DateStep::Seconds(seconds) => {
+                self.start.checked_add_signed(TimeDelta::try_seconds(seconds.checked_mul(self.index as i64)?)?)
+            }
+            DateStep::Months(months) => self.start.checked_add_months(Months::new(months.checked_mul(self.index)?)),
+        }
+    }
+}
+
+impl Iterator for DatesIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let datetime = self.nth_datetime().filter(|datetime| *datetime <= self.end)?;
+        self.index = self.index.checked_add(1)?;
+        let text = datetime.format_with_items(self.items.iter()).to_string();
+        Some(match &self.fmt {
+            Some(fmt) => {
+                match fmt_args(fmt, &[("v", FmtArg::from(text)), ("ts", FmtArg::from(datetime.and_utc().timestamp()))])
+                {
+                    Ok(string) => string,
+                    Err(err) => err.termination(),
+                }
+            }
+            None => text,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates(start: &str, end: &str, step: DateStep, pattern: Option<&str>, fmt: Option<&str>) -> Vec<String> {
+        DatesIter::new(
+            parse_datetime("start", start).unwrap(),
+            parse_datetime("end", end).unwrap(),
+            step,
+            pattern.map(String::from),
+            fmt.map(String::from),
+        )
+        .unwrap()
+        .collect()
+    }
+
+    #[test]
+    fn test_parse_datetime() {
+        let expected = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap().and_hms_opt(3, 4, 5).unwrap();
+        assert_eq!(Ok(expected), parse_datetime("start", "2026-01-02T03:04:05"));
+        assert_eq!(Ok(expected), parse_datetime("start", "2026-01-02 03:04:05"));
+        assert_eq!(Ok(expected.date().and_hms_opt(3, 4, 0).unwrap()), parse_datetime("start", "2026-01-02 03:04"));
+        assert_eq!(Ok(expected.date().and_time(NaiveTime::MIN)), parse_datetime("start", "2026-01-02"));
+        assert!(parse_datetime("start", "2026-13-02").is_err());
+        assert!(parse_datetime("start", "yesterday").is_err());
+    }
+
+    #[test]
+    fn test_dates_by_day() {
+        assert_eq!(
+            vec!["2026-02-27", "2026-02-28", "2026-03-01"],
+            dates("2026-02-27", "2026-03-01", DateStep::Seconds(86400), None, None)
+        );
+        assert_eq!(
+            vec!["p_20260227", "p_20260301"],
+            dates("2026-02-27", "2026-03-02", DateStep::Seconds(2 * 86400), Some("p_%Y%m%d"), None)
+        );
+        assert!(dates("2026-03-01", "2026-02-27", DateStep::Seconds(86400), None, None).is_empty());
+    }
+
+    #[test]
+    fn test_dates_by_time() {
+        assert_eq!(
+            vec!["2026-01-01 23:30:00", "2026-01-01 23:45:00", "2026-01-02 00:00:00"],
+            dates("2026-01-01 23:30", "2026-01-02", DateStep::Seconds(900), None, None)
+        );
+        assert_eq!(
+            vec!["app-00.log", "app-06.log", "app-12.log", "app-18.log"],
+            dates("2026-01-01", "2026-01-01 23:59", DateStep::Seconds(6 * 3600), Some("%H"), Some("app-{v}.log"))
+        );
+    }
+
+    #[test]
+    fn test_dates_by_month() {
+        assert_eq!(
+            vec!["2026-01-31", "2026-02-28", "2026-03-31", "2026-04-30"],
+            dates("2026-01-31", "2026-04-30", DateStep::Months(1), None, None)
+        );
+    }
+
+    #[test]
+    fn test_dates_fmt_timestamp() {
+        assert_eq!(
+            vec!["p_20260101", "p_20260102"],
+            dates("2026-01-01", "2026-01-02", DateStep::Seconds(86400), Some("%Y%m%d"), Some("p_{v}"))
+        );
+        assert_eq!(
+            vec!["1970-01-02 86400"],
+            dates("1970-01-02", "1970-01-02", DateStep::Months(1), None, Some("{v} {ts}"))
+        );
+    }
+
+    #[test]
+    fn test_check_pattern() {
+        assert!(check_pattern("%Y-%m-%d").is_ok());
+        assert!(check_pattern("%Q").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use std::io;
+use std::io::{BufRead, BufReader, Lines, PipeReader};
+use std::process::{Child, Command, Stdio};
+
+/// 逐行读取子进程的输出，输出结束后等待子进程退出。
+/// 读取结束前被丢弃时终止子进程，由于退出状态来自终止信号，此时不检查退出状态。
+pub(in crate::input) struct ExecLines {
+    program: String,
+    child: Option<Child>,
+    lines: Lines<BufReader<PipeReader>>,
+    check: bool,
+    configs: &'static [Config],
+}
+
+impl ExecLines {
+    /// 启动子进程，如果`stderr`为`true`，则标准错误输出与标准输出合并读取。
+    pub(in crate::input) fn spawn(
+        program: String, args: Vec<String>, stderr: bool, check: bool, configs: &'static [Config],
+    ) -> Result<Self, RpErr> {
+        let spawn_err = |err: io::Error| RpErr::SpawnCmdErr { cmd: program.clone(), err: err.to_string() };
+        let (reader, writer) = io::pipe().map_err(spawn_err)?;
+        let mut command = Command::new(&program);
+        command.args(args).stdin(Stdio::null());
+        if stderr {
+            command.stderr(writer.try_clone().map_err(spawn_err)?);
+        }
+        command.stdout(writer);
+        let child = command.spawn().map_err(spawn_err)?;
+        // 释放`command`持有的管道写入端，否则子进程退出后读取端无法结束
+        drop(command);
+        Ok(ExecLines { program, child: Some(child), lines: BufReader::new(reader).lines(), check, configs })
+    }
+
+    fn wait(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            match child.wait() {
+                Ok(status) if status.success() || !self.check => {}
+                Ok(status) => {
+                    if !skip_err(self.configs) {
+                        RpErr::CmdExitErr { cmd: self.program.clone(), status: status.to_string() }.termination()
+                    }
+                }
+                Err(err) => {
+                    if !skip_err(self.configs) {
+                        RpErr::CmdExitErr { cmd: self.program.clone(), status: err.to_string() }.termination()
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Iterator for ExecLines {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.lines.next() {
+                Some(Ok(line)) => return Some(line),
+                Some(Err(err)) => {
+                    if !skip_err(self.configs) {
+                        RpErr::ReadFromCmdErr { cmd: self.program.clone(), err: err.to_string() }.termination()
+                    }
+                }
+                None => {
+                    self.wait();
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ExecLines {
+    fn drop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str, stderr: bool, check: bool) -> Result<ExecLines, RpErr> {
+        ExecLines::spawn("sh".to_string(), vec!["-c".to_string(), script.to_string()], stderr, check, &[])
+    }
+
+    #[test]
+    fn test_exec_stdout() {
+        let lines = sh("echo a; echo b >&2; echo c", false, false).unwrap();
+        assert_eq!(vec!["a", "c"], lines.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_exec_with_stderr() {
+        let lines = sh("echo a; echo b >&2; echo c", true, false).unwrap();
+        assert_eq!(vec!["a", "b", "c"], lines.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_exec_ignore_exit_status() {
+        let lines = sh("echo a; exit 3", false, false).unwrap();
+        assert_eq!(vec!["a"], lines.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_exec_dropped_early() {
+        let mut lines = sh("echo a; exec sleep 30", false, true).unwrap();
+        assert_eq!(Some("a".to_string()), lines.next());
+        let start = std::time::Instant::now();
+        drop(lines);
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_exec_spawn_err() {
+        assert!(matches!(
+            ExecLines::spawn("rp-not-exists-program".to_string(), vec![], false, false, &[]),
+            Err(RpErr::SpawnCmdErr { cmd, .. }) if cmd == "rp-not-exists-program"
+        ));
+    }
+}
@@ -0,0 +1,288 @@
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use crate::pipe::{ItemMeta, MetaCell};
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// 所有文件均无新内容时，再次检查前的等待时间。
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 持续跟踪单个文件新增的行，类似`tail -f`。
+#[derive(Debug)]
+pub(in crate::input) struct Follower {
+    file: String,
+    reader: BufReader<File>,
+    /// 已从当前打开的文件中读取的字节数，用于检测文件截断。
+    pos: u64,
+    /// 已读取但尚未遇到换行符的内容。
+    pending: String,
+    /// 最近返回的行在当前文件中的行号，文件被截断或轮转后重新从1开始。
+    line_no: usize,
+    /// 轮转时返回旧文件剩余的不完整行之后，下一行的行号需要重新从1开始。
+    restart_line_no: bool,
+    /// 当前打开文件的inode，用于检测文件轮转。
+    #[cfg(unix)]
+    ino: u64,
+    /// 是否为命名管道，命名管道无法定位，也不会被截断或轮转。
+    #[cfg(unix)]
+    fifo: bool,
+}
+
+impl Follower {
+    pub(in crate::input) fn open(file: String) -> io::Result<Self> {
+        // 命名管道在有写入端打开前会阻塞
+        let fin = File::open(&file)?;
+        #[cfg(unix)]
+        let (ino, fifo) = {
+            let meta = fin.metadata()?;
+            (std::os::unix::fs::MetadataExt::ino(&meta), std::os::unix::fs::FileTypeExt::is_fifo(&meta.file_type()))
+        };
+        Ok(Follower {
+            file,
+            reader: BufReader::new(fin),
+            pos: 0,
+            pending: String::new(),
+            line_no: 0,
+            restart_line_no: false,
+            #[cfg(unix)]
+            ino,
+            #[cfg(unix)]
+            fifo,
+        })
+    }
+
+    /// 尝试读取下一个完整的行，不阻塞，如果暂时没有新的完整行则返回`Ok(None)`。
+    pub(in crate::input) fn poll_line(&mut self) -> io::Result<Option<String>> {
+        loop {
+            let read = self.reader.read_line(&mut self.pending)?;
+            if read == 0 {
+                break;
+            }
+            self.pos += read as u64;
+            if self.pending.ends_with('\n') {
+                let mut line = std::mem::take(&mut self.pending);
+                line.pop();
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+                self.line_no = if std::mem::take(&mut self.restart_line_no) { 1 } else { self.line_no + 1 };
+                return Ok(Some(line));
+            }
+        }
+        // 已读到文件结尾，检查文件是否被截断或轮转
+        self.check_reopen()
+    }
+
+    /// 检查文件是否被截断或轮转，如果被轮转，则旧文件中剩余的不完整行作为最后一行返回。
+    fn check_reopen(&mut self) -> io::Result<Option<String>> {
+        // 命名管道的写入端全部关闭后读取结果为空，之后有新的写入端时可以继续读取
+        #[cfg(unix)]
+        if self.fifo {
+            return Ok(None);
+        }
+        // 轮转过程中文件可能暂时不存在，等待下次检查
+        let Ok(meta) = std::fs::metadata(&self.file) else { return Ok(None) };
+        if self.is_rotated(&meta) {
+            let Ok(fin) = File::open(&self.file) else { return Ok(None) };
+            #[cfg(unix)]
+            {
+                self.ino = std::os::unix::fs::MetadataExt::ino(&fin.metadata()?);
+            }
+            self.reader = BufReader::new(fin);
+            self.pos = 0;
+            if !self.pending.is_empty() {
+                self.line_no += 1;
+                self.restart_line_no = true;
+                return Ok(Some(std::mem::take(&mut self.pending)));
+            }
+            self.line_no = 0;
+        } else if meta.len() < self.pos {
+            self.reader.seek(SeekFrom::Start(0))?;
+            self.pos = 0;
+            self.pending.clear();
+            self.line_no = 0;
+        }
+        Ok(None)
+    }
+
+    #[cfg(unix)]
+    fn is_rotated(&self, meta: &std::fs::Metadata) -> bool {
+        std::os::unix::fs::MetadataExt::ino(meta) != self.ino
+    }
+
+    #[cfg(not(unix))]
+    fn is_rotated(&self, _meta: &std::fs::Metadata) -> bool {
+        false
+    }
+}
+
+/// 依次读取各个文件的全部内容，然后轮流等待各个文件的新增行，永不结束。
+pub(in crate::input) struct FollowLines {
+    followers: Vec<Follower>,
+    /// 各个文件的名称，所有行共享。
+    files: Vec<Rc<String>>,
+    current: usize,
+    /// 当前行的来源文件和行号。
+    meta: MetaCell,
+    configs: &'static [Config],
+}
+
+impl FollowLines {
+    pub(in crate::input) fn new(followers: Vec<Follower>, configs: &'static [Config]) -> Self {
+        let files = followers.iter().map(|follower| Rc::new(follower.file.clone())).collect();
+        FollowLines { followers, files, current: 0, meta: MetaCell::default(), configs }
+    }
+
+    pub(in crate::input) fn meta(&self) -> MetaCell {
+        self.meta.clone()
+    }
+}
+
+impl Iterator for FollowLines {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.followers.is_empty() {
+            return None;
+        }
+        let mut idle = 0;
+        loop {
+            let follower = &mut self.followers[self.current];
+            match follower.poll_line() {
+                Ok(Some(line)) => {
+                    let file = self.files[self.current].clone();
+                    *self.meta.borrow_mut() = Some(ItemMeta { file, line: follower.line_no, fields: None });
+                    return Some(line);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    if !skip_err(self.configs) {
+                        RpErr::ReadFromFileErr {
+                            file: follower.file.clone(),
+                            line_no: follower.line_no,
+                            err: err.to_string(),
+                        }
+                        .termination();
+                    }
+                }
+            }
+            self.current = (self.current + 1) % self.followers.len();
+            idle += 1;
+            if idle == self.followers.len() {
+                idle = 0;
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn temp_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rp_follow_{}_{name}", std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn append(path: &PathBuf, content: &str) {
+        OpenOptions::new().append(true).open(path).unwrap().write_all(content.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_follow_appended_lines() {
+        let path = temp_file("append", "line1\nline2\n");
+        let mut follower = Follower::open(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(Some("line1".to_string()), follower.poll_line().unwrap());
+        assert_eq!(Some("line2".to_string()), follower.poll_line().unwrap());
+        assert_eq!(None, follower.poll_line().unwrap());
+        append(&path, "line3\r\npart");
+        assert_eq!(Some("line3".to_string()), follower.poll_line().unwrap());
+        assert_eq!(None, follower.poll_line().unwrap());
+        append(&path, "ial\n");
+        assert_eq!(Some("partial".to_string()), follower.poll_line().unwrap());
+        assert_eq!(None, follower.poll_line().unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_follow_truncated() {
+        let path = temp_file("truncate", "line1\nline2\n");
+        let mut follower = Follower::open(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(Some("line1".to_string()), follower.poll_line().unwrap());
+        assert_eq!(Some("line2".to_string()), follower.poll_line().unwrap());
+        std::fs::write(&path, "new\n").unwrap();
+        assert_eq!(None, follower.poll_line().unwrap());
+        assert_eq!(Some("new".to_string()), follower.poll_line().unwrap());
+        assert_eq!(1, follower.line_no);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_follow_rotated() {
+        let path = temp_file("rotate", "line1\nrest");
+        let rotated = path.with_extension("1");
+        let mut follower = Follower::open(path.to_string_lossy().to_string()).unwrap();
+        assert_eq!(Some("line1".to_string()), follower.poll_line().unwrap());
+        assert_eq!(None, follower.poll_line().unwrap());
+        std::fs::rename(&path, &rotated).unwrap();
+        std::fs::write(&path, "rotated1\nrotated2\n").unwrap();
+        assert_eq!(Some("rest".to_string()), follower.poll_line().unwrap());
+        assert_eq!(2, follower.line_no);
+        assert_eq!(Some("rotated1".to_string()), follower.poll_line().unwrap());
+        assert_eq!(Some("rotated2".to_string()), follower.poll_line().unwrap());
+        assert_eq!(2, follower.line_no);
+        assert_eq!(None, follower.poll_line().unwrap());
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(rotated).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_follow_fifo() {
+        let path = std::env::temp_dir().join(format!("rp_follow_{}_fifo", std::process::id()));
+        assert!(std::process::Command::new("mkfifo").arg(&path).status().unwrap().success());
+        let writer_path = path.clone();
+        let writer = std::thread::spawn(move || {
+            OpenOptions::new().write(true).open(&writer_path).unwrap().write_all(b"line1\nline2\n").unwrap();
+        });
+        let mut follower = Follower::open(path.to_string_lossy().to_string()).unwrap();
+        writer.join().unwrap();
+        assert_eq!(Some("line1".to_string()), follower.poll_line().unwrap());
+        assert_eq!(Some("line2".to_string()), follower.poll_line().unwrap());
+        // 写入端已关闭，不会尝试定位到开头
+        assert_eq!(None, follower.poll_line().unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_follow_lines_in_order() {
+        let path1 = temp_file("order1", "a1\na2\n");
+        let path2 = temp_file("order2", "b1\n");
+        let followers = vec![
+            Follower::open(path1.to_string_lossy().to_string()).unwrap(),
+            Follower::open(path2.to_string_lossy().to_string()).unwrap(),
+        ];
+        let mut lines = FollowLines::new(followers, &[]);
+        let meta = lines.meta();
+        let line_meta = || meta.borrow().as_ref().map(|meta| (meta.file.to_string(), meta.line));
+        let (file1, file2) = (path1.to_string_lossy().to_string(), path2.to_string_lossy().to_string());
+        assert_eq!(Some("a1".to_string()), lines.next());
+        assert_eq!(Some((file1.clone(), 1)), line_meta());
+        assert_eq!(vec!["a2", "b1"], lines.by_ref().take(2).collect::<Vec<_>>());
+        assert_eq!(Some((file2, 1)), line_meta());
+        append(&path1, "a3\n");
+        assert_eq!(Some("a3".to_string()), lines.next());
+        assert_eq!(Some((file1, 3)), line_meta());
+        std::fs::remove_file(path1).unwrap();
+        std::fs::remove_file(path2).unwrap();
+    }
+}
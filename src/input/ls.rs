@@ -0,0 +1,91 @@
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use std::path::PathBuf;
+
+/// 按深度优先顺序列出目录下的路径，同一目录下的路径按照名称排序，子目录在需要时才读取。
+pub(in crate::input) struct LsIter {
+    /// 各层目录中尚未输出的路径，逆序存放以便从尾部弹出。
+    stack: Vec<Vec<(PathBuf, bool)>>,
+    recursive: bool,
+    configs: &'static [Config],
+}
+
+impl LsIter {
+    pub(in crate::input) fn new(dir: String, recursive: bool, configs: &'static [Config]) -> Result<Self, RpErr> {
+        let entries = read_sorted_dir(PathBuf::from(dir))?;
+        Ok(LsIter { stack: vec![entries], recursive, configs })
+    }
+}
+
+impl Iterator for LsIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entries = self.stack.last_mut()?;
+            let Some((path, is_dir)) = entries.pop() else {
+                self.stack.pop();
+                continue;
+            };
+            if self.recursive && is_dir {
+                match read_sorted_dir(path.clone()) {
+                    Ok(entries) => self.stack.push(entries),
+                    Err(err) => {
+                        if !skip_err(self.configs) {
+                            err.termination()
+                        }
+                    }
+                }
+            }
+            return Some(path.to_string_lossy().into_owned());
+        }
+    }
+}
+
+/// 读取目录下的全部路径及其是否为目录（不跟随符号链接），按照名称逆序排列。
+fn read_sorted_dir(dir: PathBuf) -> Result<Vec<(PathBuf, bool)>, RpErr> {
+    let read_dir_err =
+        |err: std::io::Error| RpErr::ReadDirErr { dir: dir.to_string_lossy().into_owned(), err: err.to_string() };
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(&dir).map_err(read_dir_err)? {
+        let entry = entry.map_err(read_dir_err)?;
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        entries.push((entry.path(), is_dir));
+    }
+    entries.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ls() {
+        let root = std::env::temp_dir().join(format!("rp_ls_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("b").join("c")).unwrap();
+        std::fs::write(root.join("a.txt"), "").unwrap();
+        std::fs::write(root.join("b").join("d.txt"), "").unwrap();
+        std::fs::write(root.join("b").join("c").join("e.txt"), "").unwrap();
+        let dir = root.to_string_lossy().to_string();
+        let path = |p: &[&str]| p.iter().fold(root.clone(), |acc, p| acc.join(p)).to_string_lossy().to_string();
+
+        assert_eq!(
+            vec![path(&["a.txt"]), path(&["b"])],
+            LsIter::new(dir.clone(), false, &[]).unwrap().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![path(&["a.txt"]), path(&["b"]), path(&["b", "c"]), path(&["b", "c", "e.txt"]), path(&["b", "d.txt"])],
+            LsIter::new(dir, true, &[]).unwrap().collect::<Vec<_>>()
+        );
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_ls_not_exists() {
+        assert!(matches!(
+            LsIter::new("rp-not-exists-dir".to_string(), false, &[]),
+            Err(RpErr::ReadDirErr { dir, .. }) if dir == "rp-not-exists-dir"
+        ));
+    }
+}
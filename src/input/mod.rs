@@ -0,0 +1,739 @@
+pub(crate) mod combine;
+pub(crate) mod csv;
+pub(crate) mod dates;
+mod exec;
+mod follow;
+mod ls;
+pub(crate) mod random;
+pub(crate) mod range;
+#[cfg(unix)]
+mod socket;
+
+#[cfg(any(windows, target_os = "linux"))]
+use crate::clip;
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use crate::fmt::fmt_args;
+use crate::input::combine::ValueList;
+use crate::input::csv::CsvRecords;
+use crate::input::dates::{DateStep, DatesIter};
+use crate::input::exec::ExecLines;
+use crate::input::follow::{FollowLines, Follower};
+use crate::input::ls::LsIter;
+use crate::input::random::{RandIter, RandKind};
+use crate::input::range::GenRange;
+#[cfg(unix)]
+use crate::input::socket::SocketLines;
+use crate::pipe::{ItemMeta, MetaCell, Pipe};
+use crate::{Integer, PipeRes};
+use chrono::NaiveDateTime;
+use cmd_help::CmdHelp;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader};
+use std::iter::repeat;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, CmdHelp)]
+pub(crate) enum Input {
+    /// :in         从标准输入读取输入。
+    ///             未指定元素输入时的默认输入。
+    StdIn,
+    /// :file       从文件读取输入。
+    ///             :file <file>[ <file>][...][ follow]
+    ///                 <file>  文件路径，至少指定一个。
+    ///                         可以是命名管道，打开时阻塞直到有写入端，跟踪模式下写入端关闭后继续等待新的写入端。
+    ///                 follow  读取完已有内容后持续等待并读取新增的行，类似`tail -f`，可选。
+    ///                         检测到文件被截断或轮转（inode变化）时从头读取。
+    ///                         此模式下输入永不结束，不能与`:sort`等需要缓存全部数据的操作同时使用。
+    ///             例如：
+    ///                 :file input.txt
+    ///                 :file input1.txt input2.txt input3.txt
+    ///                 :file app.log follow
+    File { files: Vec<String>, follow: bool },
+    /// :csv        按照RFC 4180读取CSV，每条记录作为一个数据。
+    ///             支持引号包裹的字段、字段内的分隔符、换行符以及使用两个引号转义的引号。
+    ///             记录以相同的分隔符重新输出，必要时使用引号包裹字段。
+    ///             记录的各列可以在`:fmt`等格式化字符串中按照位置`{0}`或者表头中的名称引用，参考`:fmt`。
+    ///             :csv <file>|stdin[ <delimiter>][ header]
+    ///                 <file>      文件路径，必选，使用`stdin`表示从标准输入读取。
+    ///                 <delimiter> 字段分隔符，必须为单个ASCII字符，可选，未指定时使用','。
+    ///                 header      第一条记录为表头，不作为数据输出，可选。
+    ///             例如：
+    ///                 :csv users.csv
+    ///                 :csv users.csv header :fmt "{name} is {age}"
+    ///                 :csv stdin ';' header
+    Csv { file: Option<String>, delimiter: u8, header: bool },
+    /// :clip       从剪切板读取输入。
+    ///             Linux下通过外部命令读取剪切板，Wayland会话中优先使用`wl-paste`，其次依次尝试`xclip`和`xsel`，
+    ///             也可以通过环境变量`RP_CLIP_BACKEND`指定为`wayland`、`xclip`或`xsel`。
+    #[cfg(any(windows, target_os = "linux"))]
+    Clip,
+    /// :socket     监听Unix域套接字，读取连接发送的行作为输入。
+    ///             :socket <path>[ <count>]
+    ///                 <path>  套接字路径，必选。如果路径上残留了无进程监听的套接字文件，会先删除。
+    ///                 <count> 需要接受的连接数量，必须为正整数，可选。
+    ///                         未指定时持续接受新的连接，输入永不结束，不能与`:sort`等需要缓存全部数据的操作同时使用；
+    ///                         指定时接受完指定数量的连接后删除套接字文件，所有连接关闭后输入结束。
+    ///             多个连接同时读取，不同连接的行按照到达顺序交错输出。
+    ///             例如：
+    ///                 :socket /tmp/rp.sock
+    ///                 :socket /tmp/rp.sock 1
+    #[cfg(unix)]
+    Socket { path: String, count: Option<usize> },
+    /// :of         使用直接字面值作为输入。
+    ///             :of <text>[ <text][...]
+    ///                 <text>  字面值，至少指定一个，如果以':'开头，需要使用'\:'转义。
+    ///             例如：
+    ///                 :of line
+    ///                 :of line1 "line 2" 'line 3'
+    Of { values: Vec<String> },
+    /// :text       使用多行文本作为输入，每行为一个数据。
+    ///             :text <text>
+    ///                 <text>  多行文本，必选，支持'\n'、'\t'等转义，按照换行符拆分，忽略末尾的换行符，
+    ///                         'CRLF'换行时去除行尾的'\r'。
+    ///                         Token模式下还可以使用类似Shell的Here Document，从`<<<delimiter>`的下一行开始，
+    ///                         直到以<delimiter>开头的行为止，<delimiter>以单引号包围时不处理转义。
+    ///             例如：
+    ///                 :text "line1\nline2\nline3"
+    ///                 -t ':text <<EOF
+    ///                 line1
+    ///                 line2
+    ///                 EOF :upper'
+    Text { text: String },
+    /// :gen        生成指定范围内的整数、浮点数或字符作为输入，支持进一步格式化。
+    ///             :gen <start>[,[<end>][,<step>]][ <fmt>]
+    ///                 <start> 起始值，包含，必须。
+    ///                         起始值、结束值或步长中任意一个为浮点数时，生成浮点数，
+    ///                         按照起始值和步长的小数位数舍入，不会累积误差。
+    ///                         起始值为单个非数字字符时，生成字符，字符可以使用单引号或双引号包裹，
+    ///                         此时步长必须为整数，表示码点的间隔。
+    ///                 <end>   结束值，包含，可选。
+    ///                         未指定时生成到整数最大值（取决于构建版本）、正无穷或最大字符。
+    ///                         浮点数逆序生成时必须指定。
+    ///                         如果范围为空（起始值大于结束值），则无数据生成。
+    ///                 <step>  步长，不能为0，可选，未指定时取步长为1。
+    ///                         如果步长为正值，表示正序生成；
+    ///                         如果步长为负值，表示逆序生成。
+    ///                 <fmt>   格式化字符串，以{v}表示生成的值。
+    ///                         更多格式化信息参考`-h fmt`。
+    ///             例如：
+    ///                 :gen 0          生成：0 1 2 3 4 5 ...
+    ///                 :gen 0,         生成：0 1 2 3 4 5 ...
+    ///                 :gen 0,10       生成：0 1 2 3 4 5 6 7 8 9
+    ///                 :gen 0,10,2     生成：0 2 4 6 8
+    ///                 :gen 0,,2       生成：0 2 4 6 8 10 12 14 ...
+    ///                 :gen 10,0       无数据生成
+    ///                 :gen 0,10,-1    生成：9 8 7 6 5 4 3 2 1
+    ///                 :gen 0,10 n{v}  生成：n0 n1 n2 n3 n4 n5 n6 n7 n8 n9
+    ///                 :gen 0,10 "Hex of {v} is {v:#04x}" 生成：
+    ///                                 "Hex of 0 is 0x00"
+    ///                                 "Hex of 1 is 0x01"
+    ///                                 ...
+    ///                 :gen 0,1,0.25   生成：0 0.25 0.5 0.75 1
+    ///                 :gen 0,1,-0.5 {v:.2} 生成：1.00 0.50 0.00
+    ///                 :gen a,e        生成：a b c d e
+    ///                 :gen 'α','ε',2  生成：α γ ε
+    Gen { range: GenRange, fmt: Option<String> },
+    /// :repeat     重复字面值作为输入。
+    ///             :repeat <value>[ <count>]
+    ///                 <value> 需要重复的字面值，必选。
+    ///                 <count> 需要重复的次数，必须为非负数，可选，未指定时重复无限次数。
+    Repeat { value: String, count: Option<usize> },
+    /// :exec       执行外部命令，使用其标准输出作为输入。
+    ///             :exec[ stderr][ check] <program>[ <arg>][...]
+    ///                 stderr      同时读取命令的标准错误输出，可选，未指定时标准错误输出直接打印到终端。
+    ///                 check       命令以非0状态退出时报错，可选，未指定时忽略命令的退出状态。
+    ///                             后续命令提前结束读取（例如`:limit`）时终止命令，此时不检查退出状态。
+    ///                 <program>   需要执行的程序，必选。
+    ///                 <arg>       程序参数，可选，如果以':'开头，需要使用'\:'转义。
+    ///             例如：
+    ///                 :exec ls -l
+    ///                 :exec stderr check cargo build
+    ///                 :exec git log --format=%s
+    Exec { program: String, args: Vec<String>, stderr: bool, check: bool },
+    /// :env        使用环境变量作为输入，每个变量输出为`KEY=VALUE`，按照变量名排序。
+    ///             :env[ <prefix>]
+    ///                 <prefix>    变量名前缀，可选，未指定时输出全部变量。
+    ///             例如：
+    ///                 :env
+    ///                 :env CARGO_
+    Env { prefix: Option<String> },
+    /// :ls         列出目录下的路径作为输入，同一目录下的路径按照名称排序。
+    ///             :ls <dir>[ recursive]
+    ///                 <dir>       目录路径，必选。
+    ///                 recursive   递归列出子目录中的路径，可选，不会进入指向目录的符号链接。
+    ///             例如：
+    ///                 :ls .
+    ///                 :ls src recursive
+    Ls { dir: String, recursive: bool },
+    /// :args       按照分隔符拆分字面值作为输入。
+    ///             :args <value>[ <sep>]
+    ///                 <value> 需要拆分的字面值，必选。
+    ///                 <sep>   分隔符，不能为空，可选，未指定时使用系统路径列表分隔符（Windows为';'，其他为':'）。
+    ///             例如：
+    ///                 :args "$PATH"
+    ///                 :args a,b,c ,
+    Args { value: String, sep: Option<String> },
+    /// :rand       生成随机数据作为输入。
+    ///             :rand[ <count>][ seed <seed>] <kind>
+    ///                 <count> 生成的数量，必须为非负整数，可选，未指定时无限生成。
+    ///                 <seed>  随机种子，必须为非负整数，可选，指定后相同版本的输出可复现。
+    ///                 <kind>  随机数据的种类，必选：
+    ///                     int <min>,<max>         闭区间内的随机整数。
+    ///                     float <min>,<max>       左闭右开区间内的随机浮点数。
+    ///                     str <len>[,<max_len>][ <charset>]
+    ///                                             随机字符串，指定<max_len>时长度在闭区间内随机。
+    ///                                             <charset>为字符集，支持`a-z`形式的范围，
+    ///                                             未指定时为`a-zA-Z0-9`。
+    ///                     uuid                    随机UUID（版本4）。
+    ///                     pick <value>[ <value>][...]
+    ///                                             从给定的值中随机选择。
+    ///             例如：
+    ///                 :rand 10 int 1,100
+    ///                 :rand 10 seed 42 float 0,1
+    ///                 :rand 5 str 8
+    ///                 :rand 5 str 4,8 a-f0-9
+    ///                 :rand 3 uuid
+    ///                 :rand 10 pick red green blue
+    Rand { kind: RandKind, count: Option<usize>, seed: Option<u64> },
+    /// :dates      按照步长生成日期时间序列作为输入，包含结束值。
+    ///             :dates <start> <end> <step>[ <pattern>][ fmt <fmt>]
+    ///                 <start>     起始日期时间，必选，格式为`YYYY-MM-DD`、`YYYY-MM-DDTHH:MM`或
+    ///                             `YYYY-MM-DDTHH:MM:SS`，`T`也可以使用空格代替。
+    ///                 <end>       结束日期时间，必选，格式同<start>，小于<start>时没有输出。
+    ///                 <step>      步长，必须为正整数加单位，必选，单位支持：
+    ///                                 s（秒）、m（分钟）、h（小时）、d（天）、w（周）、mo（月）、y（年）。
+    ///                             按月或年递增时，目标月份没有对应日期则使用该月最后一天。
+    ///                 <pattern>   strftime风格的日期时间格式，不能为`fmt`，可选，未指定时：
+    ///                                 步长为整天且起始时间为零点时为`%Y-%m-%d`，
+    ///                                 其他情况为`%Y-%m-%d %H:%M:%S`。
+    ///                 <fmt>       格式化字符串，可选，支持以下参数：
+    ///                                 {v}     按照<pattern>格式化后的日期时间。
+    ///                                 {ts}    Unix时间戳（秒，按UTC计算）。
+    ///                             更多格式化信息参考`-h fmt`。
+    ///             例如：
+    ///                 :dates 2024-01-01 2024-01-31 1d
+    ///                 :dates 2024-01-01 2024-01-02 15m %H:%M
+    ///                 :dates 2024-01-01 2024-12-01 1mo %Y%m fmt logs/{v}.log
+    ///                 :dates 2024-01-01 2024-01-03 1d fmt "p_{v} {ts}"
+    ///                 :dates "2024-01-01 08:00" "2024-01-01 18:00" 2h %H fmt "{v} {ts}"
+    Dates { start: NaiveDateTime, end: NaiveDateTime, step: DateStep, pattern: Option<String>, fmt: Option<String> },
+    /// :product    按需生成多组候选值的笛卡尔积作为输入，可以与`:limit`配合使用。
+    ///             :product <list>[ <list>][...][ fmt <fmt>]
+    ///                 <list>  候选值列表，格式为`[<name>=]<value>[,<value>][...]`，至少指定一个。
+    ///                         指定<name>时可以在格式化字符串中以`{<name>}`引用该列表的值，
+    ///                         <name>必须为合法标识符。
+    ///                 <fmt>   格式化字符串，以`{0}`、`{1}`等按位置引用各列表的值，`{}`依次引用，
+    ///                         可选，未指定时以空格连接各个值。更多格式化信息参考`-h fmt`。
+    ///             例如：
+    ///                 :product a,b 1,2            生成："a 1" "a 2" "b 1" "b 2"
+    ///                 :product host=x,y port=80,443 fmt "{host}:{port}"
+    ///                                             生成：x:80 x:443 y:80 y:443
+    ///                 :product a,b 1,2 fmt {1}{0} 生成：1a 2a 1b 2b
+    Product { lists: Vec<ValueList>, fmt: Option<String> },
+    /// :permutations
+    ///             按需生成候选值中<k>个元素的排列作为输入。
+    ///             :permutations <k> <values>[ fmt <fmt>]
+    ///                 <k>         每个排列的元素个数，必须为正整数，必选。
+    ///                             大于候选值个数时无数据生成。
+    ///                 <values>    候选值列表，格式为`<value>[,<value>][...]`，必选。
+    ///                 <fmt>       格式化字符串，以`{0}`、`{1}`等按位置引用排列中的值，`{}`依次引用，
+    ///                             可选，未指定时以空格连接各个值。
+    ///             例如：
+    ///                 :permutations 2 a,b,c       生成："a b" "a c" "b a" "b c" "c a" "c b"
+    Permutations { k: usize, values: Vec<String>, fmt: Option<String> },
+    /// :combinations
+    ///             按需生成候选值中<k>个元素的组合作为输入，组合内元素保持原有顺序。
+    ///             :combinations <k> <values>[ fmt <fmt>]
+    ///                 <k>         每个组合的元素个数，必须为正整数，必选。
+    ///                             大于候选值个数时无数据生成。
+    ///                 <values>    候选值列表，格式为`<value>[,<value>][...]`，必选。
+    ///                 <fmt>       格式化字符串，同`:permutations`。
+    ///             例如：
+    ///                 :combinations 2 a,b,c fmt {0}-{1}
+    ///                                             生成：a-b a-c b-c
+    Combinations { k: usize, values: Vec<String>, fmt: Option<String> },
+    /// :cat        依次连接多个输入命令的数据作为输入，前一个输入结束后才打开并读取后一个输入。
+    ///             连续指定多个输入命令时自动连接，`:cat`可以省略。
+    ///             [:cat ]<input_cmd>[ <input_cmd>][...]
+    ///                 <input_cmd> 除`:cat`以外的输入命令，至少指定一个。
+    ///             例如：
+    ///                 :of header :file body.txt :of footer
+    ///                 :cat :in :gen 1,3
+    Cat { inputs: Vec<Input> },
+}
+
+impl Input {
+    pub(crate) fn new_std_in() -> Input {
+        Input::StdIn
+    }
+    pub(crate) fn new_file(files: Vec<String>, follow: bool) -> Input {
+        Input::File { files, follow }
+    }
+
+    #[cfg(any(windows, target_os = "linux"))]
+    pub(crate) fn new_clip() -> Input {
+        Input::Clip
+    }
+    pub(crate) fn new_of(values: Vec<String>) -> Input {
+        Input::Of { values }
+    }
+    pub(crate) fn new_text(text: String) -> Input {
+        Input::Text { text }
+    }
+    pub(crate) fn new_gen(range: GenRange, fmt: Option<String>) -> Input {
+        Input::Gen { range, fmt }
+    }
+    #[cfg(unix)]
+    pub(crate) fn new_socket(path: String, count: Option<usize>) -> Input {
+        Input::Socket { path, count }
+    }
+    pub(crate) fn new_csv(file: Option<String>, delimiter: u8, header: bool) -> Input {
+        Input::Csv { file, delimiter, header }
+    }
+    pub(crate) fn new_repeat(value: String, count: Option<usize>) -> Input {
+        Input::Repeat { value, count }
+    }
+    pub(crate) fn new_exec(program: String, args: Vec<String>, stderr: bool, check: bool) -> Input {
+        Input::Exec { program, args, stderr, check }
+    }
+    pub(crate) fn new_env(prefix: Option<String>) -> Input {
+        Input::Env { prefix }
+    }
+    pub(crate) fn new_ls(dir: String, recursive: bool) -> Input {
+        Input::Ls { dir, recursive }
+    }
+    pub(crate) fn new_args(value: String, sep: Option<String>) -> Result<Input, RpErr> {
+        if let Some(sep) = &sep
+            && sep.is_empty()
+        {
+            return Err(RpErr::ArgParseErr {
+                cmd: ":args",
+                arg: "sep",
+                arg_value: sep.clone(),
+                error: "separator must not be empty".to_string(),
+            });
+        }
+        Ok(Input::Args { value, sep })
+    }
+    pub(crate) fn new_rand(kind: RandKind, count: Option<usize>, seed: Option<u64>) -> Input {
+        Input::Rand { kind, count, seed }
+    }
+    pub(crate) fn new_dates(
+        start: NaiveDateTime, end: NaiveDateTime, step: DateStep, pattern: Option<String>, fmt: Option<String>,
+    ) -> Input {
+        Input::Dates { start, end, step, pattern, fmt }
+    }
+    pub(crate) fn new_product(lists: Vec<ValueList>, fmt: Option<String>) -> Input {
+        Input::Product { lists, fmt }
+    }
+    pub(crate) fn new_permutations(k: usize, values: Vec<String>, fmt: Option<String>) -> Input {
+        Input::Permutations { k, values, fmt }
+    }
+    pub(crate) fn new_combinations(k: usize, values: Vec<String>, fmt: Option<String>) -> Input {
+        Input::Combinations { k, values, fmt }
+    }
+    pub(crate) fn new_cat(inputs: Vec<Input>) -> Input {
+        Input::Cat { inputs }
+    }
+}
+
+impl Input {
+    /// 输入是否持续跟踪新增数据而永不结束。
+    pub(crate) fn is_follow(&self) -> bool {
+        match self {
+            Input::File { follow, .. } => *follow,
+            #[cfg(unix)]
+            Input::Socket { count, .. } => count.is_none(),
+            Input::Cat { inputs } => inputs.iter().any(Input::is_follow),
+            _ => false,
+        }
+    }
+
+    /// 输入是否随时间逐渐产生数据，此时每个数据输出后都需要立即刷新标准输出。
+    pub(crate) fn is_streaming(&self) -> bool {
+        match self {
+            Input::File { follow, .. } => *follow,
+            #[cfg(unix)]
+            Input::Socket { .. } => true,
+            Input::Exec { .. } => true,
+            Input::Cat { inputs } => inputs.iter().any(Input::is_streaming),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn try_into(self, configs: &'static [Config]) -> PipeRes {
+        match self {
+            Input::StdIn => Ok(Pipe {
+                iter: Box::new(io::stdin().lock().lines().take_while(Result::is_ok).map(|line| line.unwrap())),
+                meta: None,
+            }),
+            Input::File { files, follow: true } => {
+                let lines = FollowLines::new(
+                    files
+                        .into_iter()
+                        .filter_map(|f| match Follower::open(f.clone()) {
+                            Ok(follower) => Some(follower),
+                            Err(err) => {
+                                if skip_err(configs) {
+                                    None
+                                } else {
+                                    RpErr::OpenFileErr { file: f, err: err.to_string() }.termination();
+                                }
+                            }
+                        })
+                        .collect(),
+                    configs,
+                );
+                let meta = lines.meta();
+                Ok(Pipe { iter: Box::new(lines), meta: Some(meta) })
+            }
+            Input::File { files, follow: false } => {
+                let meta = MetaCell::default();
+                let writer = meta.clone();
+                Ok(Pipe {
+                    iter: Box::new(
+                        files
+                            .into_iter()
+                            .map(|f| (File::open(&f), f))
+                            .filter_map(|(r, f)| match r {
+                                Ok(fin) => Some((fin, f)),
+                                Err(err) => {
+                                    if skip_err(configs) {
+                                        None
+                                    } else {
+                                        RpErr::OpenFileErr { file: f, err: err.to_string() }.termination();
+                                    }
+                                }
+                            })
+                            .map(|(fin, f)| (BufReader::new(fin), Rc::new(f)))
+                            .flat_map(|(reader, f)| BufRead::lines(reader).enumerate().map(move |l| (l, f.clone())))
+                            .filter_map(move |((line_no, lr), f)| match lr {
+                                Ok(line) => {
+                                    *writer.borrow_mut() = Some(ItemMeta { file: f, line: line_no + 1, fields: None });
+                                    Some(line)
+                                }
+                                Err(err) => {
+                                    if skip_err(configs) {
+                                        None
+                                    } else {
+                                        RpErr::ReadFromFileErr { file: (*f).clone(), line_no, err: err.to_string() }
+                                            .termination();
+                                    }
+                                }
+                            }),
+                    ),
+                    meta: Some(meta),
+                })
+            }
+            #[cfg(any(windows, target_os = "linux"))]
+            Input::Clip => Ok(Pipe { iter: Box::new(OwnedSplitLines::new(clip::get_text()?)), meta: None }),
+            #[cfg(unix)]
+            Input::Socket { path, count } => {
+                Ok(Pipe { iter: Box::new(SocketLines::bind(path, count, configs)?), meta: None })
+            }
+            Input::Csv { file, delimiter, header } => {
+                let records = CsvRecords::open(file, delimiter, header, configs)?;
+                let meta = records.meta();
+                Ok(Pipe { iter: Box::new(records), meta: Some(meta) })
+            }
+            Input::Of { values } => Ok(Pipe { iter: Box::new(values.into_iter()), meta: None }),
+            Input::Text { text } => {
+                let lines = text.lines().map(String::from).collect::<Vec<_>>();
+                Ok(Pipe { iter: Box::new(lines.into_iter()), meta: None })
+            }
+            Input::Gen { range, fmt } => {
+                if let Some(fmt) = fmt {
+                    Ok(Pipe {
+                        iter: Box::new(range.into_iter().map(move |x| match fmt_args(&fmt, &[("v", x)]) {
+                            Ok(string) => string,
+                            Err(err) => err.termination(),
+                        })),
+                        meta: None,
+                    })
+                } else {
+                    Ok(Pipe { iter: Box::new(range.into_iter().map(|s| s.to_string())), meta: None })
+                }
+            }
+            Input::Repeat { value, count } => Ok(if let Some(count_value) = count {
+                Pipe { iter: Box::new(std::iter::repeat_n(value, count_value)), meta: None }
+            } else {
+                Pipe { iter: Box::new(repeat(value)), meta: None }
+            }),
+            Input::Exec { program, args, stderr, check } => {
+                Ok(Pipe { iter: Box::new(ExecLines::spawn(program, args, stderr, check, configs)?), meta: None })
+            }
+            Input::Env { prefix } => {
+                let mut vars = std::env::vars_os()
+                    .map(|(k, v)| (k.to_string_lossy().into_owned(), v.to_string_lossy().into_owned()))
+                    .filter(|(k, _)| prefix.as_ref().is_none_or(|prefix| k.starts_with(prefix)))
+                    .collect::<Vec<_>>();
+                vars.sort_unstable();
+                Ok(Pipe { iter: Box::new(vars.into_iter().map(|(k, v)| format!("{k}={v}"))), meta: None })
+            }
+            Input::Ls { dir, recursive } => {
+                Ok(Pipe { iter: Box::new(LsIter::new(dir, recursive, configs)?), meta: None })
+            }
+            Input::Args { value, sep } => {
+                let sep = sep.unwrap_or_else(|| if cfg!(windows) { ";" } else { ":" }.to_string());
+                let items = value.split(&sep).map(String::from).collect::<Vec<_>>();
+                Ok(Pipe { iter: Box::new(items.into_iter()), meta: None })
+            }
+            Input::Rand { kind, count, seed } => {
+                Ok(Pipe { iter: Box::new(RandIter::new(kind, count, seed)), meta: None })
+            }
+            Input::Dates { start, end, step, pattern, fmt } => {
+                Ok(Pipe { iter: Box::new(DatesIter::new(start, end, step, pattern, fmt)?), meta: None })
+            }
+            Input::Product { lists, fmt } => Ok(Pipe { iter: combine::product(lists, fmt), meta: None }),
+            Input::Permutations { k, values, fmt } => {
+                Ok(Pipe { iter: combine::permutations(k, values, fmt), meta: None })
+            }
+            Input::Combinations { k, values, fmt } => {
+                Ok(Pipe { iter: combine::combinations(k, values, fmt), meta: None })
+            }
+            Input::Cat { inputs } => {
+                // 每个数据源有各自的来源信息，在产生数据时同步到拼接后的来源信息中。
+                let meta = MetaCell::default();
+                let writer = meta.clone();
+                // 读取到某个数据源时才打开它，避免提前启动命令或监听套接字
+                let iter = inputs.into_iter().flat_map(move |input| {
+                    let pipe = match input.try_into(configs) {
+                        Ok(pipe) => pipe,
+                        Err(_) if skip_err(configs) => Pipe { iter: Box::new(std::iter::empty()), meta: None },
+                        Err(err) => err.termination(),
+                    };
+                    let (writer, source) = (writer.clone(), pipe.meta);
+                    pipe.iter.inspect(move |_| {
+                        *writer.borrow_mut() = source.as_ref().and_then(|cell| cell.borrow().clone());
+                    })
+                });
+                Ok(Pipe { iter: Box::new(iter), meta: Some(meta) })
+            }
+        }
+    }
+}
+
+fn range_to_iter(start: Integer, end: Integer, step: Integer) -> Box<dyn DoubleEndedIterator<Item = Integer>> {
+    let iter = RangeIter { start, end, step: Integer::abs(step), next: start, next_back: end };
+    if step < 0 { Box::new(iter.rev()) } else { Box::new(iter) }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct RangeIter {
+    start: Integer,
+    end: Integer,
+    step: Integer,
+    next: Integer,
+    next_back: Integer,
+}
+
+impl Iterator for RangeIter {
+    type Item = Integer;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.start && self.next <= self.end && self.next <= self.next_back {
+            let res = Some(self.next);
+            self.next += self.step;
+            res
+        } else {
+            None
+        }
+    }
+}
+
+impl DoubleEndedIterator for RangeIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next_back >= self.start && self.next_back <= self.end && self.next_back >= self.next {
+            let res = Some(self.next_back);
+            self.next_back -= self.step;
+            res
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(not(any(windows, target_os = "linux")), allow(dead_code))]
+struct OwnedSplitLines {
+    text: String,
+    pos: usize,
+}
+
+#[cfg_attr(not(any(windows, target_os = "linux")), allow(dead_code))]
+impl OwnedSplitLines {
+    fn new(text: String) -> Self {
+        Self { text, pos: 0 }
+    }
+}
+
+impl Iterator for OwnedSplitLines {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos > self.text.len() {
+            return None;
+        }
+        let rest = &self.text[self.pos..];
+        let newline_pos = rest.find('\n');
+        match newline_pos {
+            Some(idx) => {
+                let line = rest[..idx].to_string();
+                self.pos += idx + 1;
+                Some(line)
+            }
+            None if self.pos < self.text.len() => {
+                let line = rest.to_string();
+                self.pos = self.text.len() + 1;
+                Some(line)
+            }
+            None if self.pos == self.text.len() => {
+                self.pos += 1;
+                Some(String::new())
+            }
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod iter_tests {
+    use super::*;
+
+    #[test]
+    fn test_cat() {
+        let input = Input::new_cat(vec![
+            Input::new_of(vec!["header".to_string()]),
+            Input::new_gen(GenRange::Integer(1, 3, 1), None),
+            Input::new_of(vec!["footer".to_string()]),
+        ]);
+        assert!(!input.is_follow());
+        let pipe = input.try_into(&[]).unwrap();
+        assert_eq!(vec!["header", "1", "2", "3", "footer"], pipe.iter.collect::<Vec<_>>());
+
+        let input = Input::new_cat(vec![
+            Input::new_of(vec!["a".to_string()]),
+            Input::new_ls("rp-not-exists-dir".to_string(), false),
+        ]);
+        // 后一个输入在读取到时才打开
+        let mut pipe = input.try_into(&[]).unwrap();
+        assert_eq!(Some("a".to_string()), pipe.iter.next());
+
+        let input = Input::new_cat(vec![
+            Input::new_ls("rp-not-exists-dir".to_string(), false),
+            Input::new_of(vec!["b".to_string()]),
+        ]);
+        let pipe = input.try_into(&[Config::SkipErr]).unwrap();
+        assert_eq!(vec!["b"], pipe.iter.collect::<Vec<_>>());
+
+        let input =
+            Input::new_cat(vec![Input::new_of(vec!["a".to_string()]), Input::new_file(vec!["f".to_string()], true)]);
+        assert!(input.is_follow());
+        #[cfg(unix)]
+        {
+            assert!(Input::new_socket("s.sock".to_string(), None).is_follow());
+            assert!(!Input::new_socket("s.sock".to_string(), Some(1)).is_follow());
+            assert!(Input::new_cat(vec![Input::new_socket("s.sock".to_string(), None)]).is_follow());
+        }
+    }
+
+    #[test]
+    fn test_text() {
+        let pipe = Input::new_text("a\r\n\nb\n".to_string()).try_into(&[]).unwrap();
+        assert_eq!(vec!["a", "", "b"], pipe.iter.collect::<Vec<_>>());
+        let pipe = Input::new_text(String::new()).try_into(&[]).unwrap();
+        assert_eq!(0, pipe.iter.count());
+    }
+
+    #[test]
+    fn test_file_meta() {
+        let path = std::env::temp_dir().join(format!("rp_meta_{}.txt", std::process::id()));
+        std::fs::write(&path, "a\nb\n").unwrap();
+        let file = path.to_string_lossy().into_owned();
+        let input =
+            Input::new_cat(vec![Input::new_file(vec![file.clone()], false), Input::new_of(vec!["c".to_string()])]);
+        let pipe = input.try_into(&[]).unwrap();
+        let meta = pipe.meta.clone().unwrap();
+        let collected = pipe
+            .map(|item| (item, meta.borrow().as_ref().map(|meta| ((*meta.file).clone(), meta.line))))
+            .collect::<Vec<_>>();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(
+            vec![
+                ("a".to_string(), Some((file.clone(), 1))),
+                ("b".to_string(), Some((file.clone(), 2))),
+                ("c".to_string(), None)
+            ],
+            collected
+        );
+    }
+
+    #[test]
+    fn test_owned_split_lines_basic() {
+        let text = String::from("line1\nline2\nline3");
+        let iter = OwnedSplitLines::new(text);
+        assert_eq!(iter.collect::<Vec<_>>(), vec!["line1", "line2", "line3"]);
+    }
+
+    #[test]
+    fn test_owned_split_lines_empty() {
+        let text = String::new();
+        let iter = OwnedSplitLines::new(text);
+        assert_eq!(iter.collect::<Vec<_>>(), vec![String::new()]);
+    }
+
+    #[test]
+    fn test_owned_split_lines_trailing_newline() {
+        let text = String::from("line1\nline2\n");
+        let iter = OwnedSplitLines::new(text);
+        assert_eq!(iter.collect::<Vec<_>>(), vec!["line1", "line2", ""]);
+    }
+
+    #[test]
+    fn test_owned_split_lines_single_line() {
+        let text = String::from("single");
+        let iter = OwnedSplitLines::new(text);
+        assert_eq!(iter.collect::<Vec<_>>(), vec!["single"]);
+    }
+
+    #[test]
+    fn test_range_to_iter_positive() {
+        assert_eq!(range_to_iter(0, 10, 1).collect::<Vec<_>>(), (0..=10).collect::<Vec<_>>());
+        assert_eq!(range_to_iter(0, 10, 2).collect::<Vec<_>>(), (0..=10).step_by(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_range_to_iter_negative() {
+        assert_eq!(range_to_iter(0, 10, -1).collect::<Vec<_>>(), (0..=10).rev().collect::<Vec<_>>());
+        assert_eq!(range_to_iter(0, 10, -2).collect::<Vec<_>>(), (0..=10).rev().step_by(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_range_to_iter_empty() {
+        assert_eq!(range_to_iter(0, 0, 1).collect::<Vec<_>>(), (0..=0).collect::<Vec<_>>());
+        assert_eq!(range_to_iter(0, 0, 2).collect::<Vec<_>>(), (0..=0).step_by(2).collect::<Vec<_>>());
+    }
+
+    #[allow(clippy::reversed_empty_ranges)]
+    #[test]
+    fn test_range_to_iter_reverted_range_and_positive() {
+        assert_eq!(range_to_iter(10, 0, 1).collect::<Vec<_>>(), (10..=0).collect::<Vec<_>>());
+        assert_eq!(range_to_iter(10, 0, 2).collect::<Vec<_>>(), (10..=0).step_by(2).collect::<Vec<_>>());
+    }
+
+    #[allow(clippy::reversed_empty_ranges)]
+    #[test]
+    fn test_range_to_iter_reverted_range_and_negative() {
+        assert_eq!(range_to_iter(10, 0, -1).collect::<Vec<_>>(), (10..=0).rev().collect::<Vec<_>>());
+        assert_eq!(range_to_iter(10, 0, -2).collect::<Vec<_>>(), (10..=0).rev().step_by(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_range_to_iter_zero_step() {
+        assert_eq!(Some(0), range_to_iter(0, 0, 0).next());
+        assert_eq!(range_to_iter(0, 1, 0).take(10).collect::<Vec<_>>(), vec![0; 10].into_iter().collect::<Vec<_>>());
+        assert_eq!(range_to_iter(0, 1, 0).take(100).collect::<Vec<_>>(), vec![0; 100].into_iter().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::err::RpErr;
+use crate::{Float, Integer};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// 未指定字符集时随机字符串使用的字符集。
+const DEFAULT_CHARSET: &str = "a-zA-Z0-9";
+
+/// 随机数据的种类。
+#[derive(Debug, PartialEq)]
+pub(crate) enum RandKind {
+    /// 闭区间内的随机整数。
+    Int(Integer, Integer),
+    /// 左闭右开区间内的随机浮点数。
+    Float(Float, Float),
+    /// 由指定字符集组成的随机字符串，长度在闭区间内随机。
+    Str { min_len: usize, max_len: usize, charset: Vec<char> },
+    /// 随机UUID（版本4）。
+    Uuid,
+    /// 从给定的值中随机选择。
+    Pick(Vec<String>),
+}
+
+impl RandKind {
+    pub(crate) fn new_int(min: Integer, max: Integer) -> Result<RandKind, RpErr> {
+        if min > max {
+            Err(RpErr::ArgParseErr {
+                cmd: ":rand",
+                arg: "range",
+                arg_value: format!("{min:?},{max:?}"),
+                error: "min is greater than max".to_string(),
+            })
+        } else {
+            Ok(RandKind::Int(min, max))
+        }
+    }
+
+    pub(crate) fn new_float(min: Float, max: Float) -> Result<RandKind, RpErr> {
+        // 范围的跨度溢出时无法生成均匀分布的随机数
+        if !min.is_finite() || !max.is_finite() || !(max - min).is_finite() || min > max {
+            Err(RpErr::ArgParseErr {
+                cmd: ":rand",
+                arg: "range",
+                arg_value: format!("{min:?},{max:?}"),
+                error: "range and its span must be finite and min can not be greater than max".to_string(),
+            })
+        } else {
+            Ok(RandKind::Float(min, max))
+        }
+    }
+
+    /// 字符集中支持使用`a-z`形式的范围，开头或结尾的`-`视为普通字符。
+    pub(crate) fn new_str(min_len: usize, max_len: Option<usize>, charset: Option<String>) -> Result<RandKind, RpErr> {
+        let max_len = max_len.unwrap_or(min_len);
+        let charset_spec = charset.unwrap_or_else(|| DEFAULT_CHARSET.to_string());
+        let charset = expand_charset(&charset_spec);
+        if min_len > max_len {
+            Err(RpErr::ArgParseErr {
+                cmd: ":rand",
+                arg: "len",
+                arg_value: format!("{min_len},{max_len}"),
+                error: "min length is greater than max length".to_string(),
+            })
+        } else if charset.is_empty() {
+            Err(RpErr::ArgParseErr {
+                cmd: ":rand",
+                arg: "charset",
+                arg_value: charset_spec,
+                error: "charset is empty".to_string(),
+            })
+        } else {
+            Ok(RandKind::Str { min_len, max_len, charset })
+        }
+    }
+}
+
+fn expand_charset(spec: &str) -> Vec<char> {
+    let chars = spec.chars().collect::<Vec<_>>();
+    let mut charset = Vec::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        if idx + 2 < chars.len() && chars[idx + 1] == '-' {
+            charset.extend(chars[idx]..=chars[idx + 2]);
+            idx += 3;
+        } else {
+            charset.push(chars[idx]);
+            idx += 1;
+        }
+    }
+    charset
+}
+
+/// 生成随机数据，如果未指定数量则无限生成。
+pub(in crate::input) struct RandIter {
+    kind: RandKind,
+    remaining: Option<usize>,
+    rng: StdRng,
+}
+
+impl RandIter {
+    /// 指定种子时，相同构建版本的输出可复现。
+    pub(in crate::input) fn new(kind: RandKind, count: Option<usize>, seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        RandIter { kind, remaining: count, rng }
+    }
+}
+
+impl Iterator for RandIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(remaining) = &mut self.remaining {
+            if *remaining == 0 {
+                return None;
+            }
+            *remaining -= 1;
+        }
+        let rng = &mut self.rng;
+        Some(match &self.kind {
+            RandKind::Int(min, max) => rng.random_range(*min..=*max).to_string(),
+            RandKind::Float(min, max) => {
+                if min == max {
+                    min.to_string()
+                } else {
+                    rng.random_range(*min..*max).to_string()
+                }
+            }
+            RandKind::Str { min_len, max_len, charset } => {
+                let len = rng.random_range(*min_len..=*max_len);
+                (0..len).map(|_| charset[rng.random_range(0..charset.len())]).collect()
+            }
+            RandKind::Uuid => {
+                let mut bytes: [u8; 16] = rng.random();
+                bytes[6] = (bytes[6] & 0x0f) | 0x40; // 版本4
+                bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122变体
+                let hex = bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+                format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+            }
+            RandKind::Pick(values) => values[rng.random_range(0..values.len())].clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_charset() {
+        assert_eq!(vec!['a', 'b', 'c'], expand_charset("a-c"));
+        assert_eq!(vec!['a', 'b', '0', '1', '-'], expand_charset("a-b0-1-"));
+        assert_eq!(vec!['-', 'x'], expand_charset("-x"));
+        assert_eq!(vec!['α', 'β', 'γ'], expand_charset("α-γ"));
+        assert!(expand_charset("z-a").is_empty());
+    }
+
+    #[test]
+    fn test_rand_count_and_seed() {
+        let kind = || RandKind::new_int(1, 100).unwrap();
+        assert_eq!(5, RandIter::new(kind(), Some(5), None).count());
+        assert_eq!(0, RandIter::new(kind(), Some(0), None).count());
+        assert_eq!(
+            RandIter::new(kind(), Some(10), Some(42)).collect::<Vec<_>>(),
+            RandIter::new(kind(), Some(10), Some(42)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rand_int_and_float() {
+        for item in RandIter::new(RandKind::new_int(-3, 3).unwrap(), Some(100), None) {
+            assert!((-3..=3).contains(&item.parse::<Integer>().unwrap()));
+        }
+        for item in RandIter::new(RandKind::new_float(0.5, 1.5).unwrap(), Some(100), None) {
+            assert!((0.5..1.5).contains(&item.parse::<Float>().unwrap()));
+        }
+        assert_eq!(vec!["2"], RandIter::new(RandKind::new_float(2.0, 2.0).unwrap(), Some(1), None).collect::<Vec<_>>());
+        assert!(RandKind::new_int(3, 1).is_err());
+        assert!(RandKind::new_float(3.0, 1.0).is_err());
+        assert!(RandKind::new_float(-1e308, 1e308).is_err());
+    }
+
+    #[test]
+    fn test_rand_str() {
+        for item in RandIter::new(RandKind::new_str(2, Some(4), Some("ab".to_string())).unwrap(), Some(100), None) {
+            assert!((2..=4).contains(&item.len()));
+            assert!(item.chars().all(|c| c == 'a' || c == 'b'));
+        }
+        for item in RandIter::new(RandKind::new_str(8, None, None).unwrap(), Some(10), None) {
+            assert_eq!(8, item.len());
+            assert!(item.chars().all(|c| c.is_ascii_alphanumeric()));
+        }
+        assert!(RandKind::new_str(3, Some(1), None).is_err());
+        assert!(RandKind::new_str(3, None, Some("z-a".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_rand_uuid_and_pick() {
+        for item in RandIter::new(RandKind::Uuid, Some(10), None) {
+            assert_eq!(36, item.len());
+            assert_eq!(Some('4'), item.chars().nth(14));
+            assert!(matches!(item.chars().nth(19), Some('8' | '9' | 'a' | 'b')));
+        }
+        let values = vec!["x".to_string(), "y".to_string()];
+        for item in RandIter::new(RandKind::Pick(values.clone()), Some(10), None) {
+            assert!(values.contains(&item));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::err::RpErr;
+use crate::fmt::FmtArg;
+use crate::input::range_to_iter;
+use crate::{Float, Integer};
+
+/// `:gen`生成的范围，步长为负值时从结束值开始逆序生成。
+#[derive(Debug, PartialEq)]
+pub(crate) enum GenRange {
+    /// 整数范围：起始值、结束值、步长。
+    Integer(Integer, Integer, Integer),
+    /// 浮点数范围：起始值、结束值、步长。
+    Float(Float, Float, Float),
+    /// 字符范围：起始字符、结束字符、码点步长。
+    Char(char, char, Integer),
+}
+
+impl GenRange {
+    /// 未指定结束值时`end`为正无穷，此时不支持逆序生成。
+    pub(crate) fn new_float(start: Float, end: Float, step: Float) -> Result<GenRange, RpErr> {
+        if !start.is_finite() || end.is_nan() || end == Float::NEG_INFINITY || !step.is_finite() || step == 0.0 {
+            Err(RpErr::ArgParseErr {
+                cmd: ":gen",
+                arg: "range",
+                arg_value: format!("{start},{end},{step}"),
+                error: "start and step must be finite and step can not be zero".to_string(),
+            })
+        } else if step < 0.0 && !end.is_finite() {
+            Err(RpErr::ArgParseErr {
+                cmd: ":gen",
+                arg: "range",
+                arg_value: format!("{start},,{step}"),
+                error: "end is required when step is negative".to_string(),
+            })
+        } else {
+            Ok(GenRange::Float(start, end, step))
+        }
+    }
+
+    pub(in crate::input) fn into_iter(self) -> Box<dyn Iterator<Item = FmtArg>> {
+        match self {
+            GenRange::Integer(start, end, step) => Box::new(range_to_iter(start, end, step).map(FmtArg::from)),
+            GenRange::Float(start, end, step) => Box::new(FloatRangeIter::new(start, end, step).map(FmtArg::from)),
+            GenRange::Char(start, end, step) => Box::new(
+                range_to_iter(start as Integer, end as Integer, step)
+                    .filter_map(|code| char::from_u32(code as u32)) // 跳过代理区码点
+                    .map(|c| FmtArg::from(c.to_string())),
+            ),
+        }
+    }
+}
+
+/// 第`n`个值由起始值直接加上`n`倍步长得到，并按照起始值和步长的小数位数舍入，避免累积误差。
+struct FloatRangeIter {
+    from: Float,
+    to: Float,
+    step: Float,
+    precision: usize,
+    index: Float,
+}
+
+impl FloatRangeIter {
+    fn new(start: Float, end: Float, step: Float) -> Self {
+        let (from, to) = if step < 0.0 { (end, start) } else { (start, end) };
+        let precision = decimal_places(from).max(decimal_places(step));
+        FloatRangeIter { from, to, step, precision, index: 0.0 }
+    }
+}
+
+impl Iterator for FloatRangeIter {
+    type Item = Float;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = round_to(self.from + self.index * self.step, self.precision);
+        let in_range = if self.step < 0.0 { value >= self.to } else { value <= self.to };
+        if value.is_finite() && in_range {
+            self.index += 1.0;
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+/// 浮点数最短十进制表示的小数位数。
+fn decimal_places(value: Float) -> usize {
+    let string = value.to_string();
+    string.find('.').map_or(0, |idx| string.len() - idx - 1)
+}
+
+fn round_to(value: Float, precision: usize) -> Float {
+    format!("{value:.precision$}").parse().unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(range: GenRange) -> Vec<String> {
+        range.into_iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_float_range() {
+        assert_eq!(
+            vec!["0", "0.1", "0.2", "0.3", "0.4", "0.5", "0.6", "0.7", "0.8", "0.9", "1"],
+            collect(GenRange::new_float(0.0, 1.0, 0.1).unwrap())
+        );
+        assert_eq!(vec!["1.5", "1.75", "2"], collect(GenRange::new_float(1.5, 2.0, 0.25).unwrap()));
+        assert_eq!(vec!["1", "0.7", "0.4", "0.1"], collect(GenRange::new_float(0.0, 1.0, -0.3).unwrap()));
+        assert!(collect(GenRange::new_float(1.0, 0.0, 0.1).unwrap()).is_empty());
+        assert_eq!(
+            vec!["0", "0.1", "0.2"],
+            GenRange::new_float(0.0, Float::INFINITY, 0.1)
+                .unwrap()
+                .into_iter()
+                .take(3)
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_float_range_no_drift() {
+        let last = FloatRangeIter::new(0.0, 1000.0, 0.1).last();
+        assert_eq!(Some(1000.0), last);
+        assert_eq!(10001, FloatRangeIter::new(0.0, 1000.0, 0.1).count());
+    }
+
+    #[test]
+    fn test_new_float_invalid() {
+        assert!(GenRange::new_float(0.0, 1.0, 0.0).is_err());
+        assert!(GenRange::new_float(Float::NAN, 1.0, 0.1).is_err());
+        assert!(GenRange::new_float(0.0, Float::INFINITY, -0.1).is_err());
+    }
+
+    #[test]
+    fn test_char_range() {
+        assert_eq!(vec!["a", "b", "c"], collect(GenRange::Char('a', 'c', 1)));
+        assert_eq!(vec!["e", "c", "a"], collect(GenRange::Char('a', 'e', -2)));
+        assert_eq!(vec!["α", "β", "γ"], collect(GenRange::Char('α', 'γ', 1)));
+        assert_eq!(vec!["\u{d7ff}", "\u{e000}"], collect(GenRange::Char('\u{d7ff}', '\u{e000}', 1)));
+        assert!(collect(GenRange::Char('z', 'a', 1)).is_empty());
+    }
+
+    #[test]
+    fn test_decimal_places() {
+        assert_eq!(0, decimal_places(10.0));
+        assert_eq!(1, decimal_places(0.1));
+        assert_eq!(3, decimal_places(-2.125));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use std::io;
+use std::io::{BufRead, BufReader};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
+use std::thread;
+
+/// 读取连接的通道容量，下游较慢时各个连接最多领先此数量的行，之后阻塞读取直到下游取走数据。
+const SOCKET_CHANNEL_CAPACITY: usize = 1024;
+
+/// 监听Unix域套接字，同时读取所有连接发送的行，按照到达顺序输出。
+pub(in crate::input) struct SocketLines {
+    receiver: Receiver<Result<String, RpErr>>,
+    configs: &'static [Config],
+}
+
+impl SocketLines {
+    /// 绑定套接字，`count`为需要接受的连接数量，未指定时持续接受新的连接，永不结束。
+    pub(in crate::input) fn bind(
+        path: String, count: Option<usize>, configs: &'static [Config],
+    ) -> Result<Self, RpErr> {
+        let listener =
+            bind_listener(&path).map_err(|err| RpErr::SocketErr { path: path.clone(), err: err.to_string() })?;
+        let (sender, receiver) = sync_channel(SOCKET_CHANNEL_CAPACITY);
+        thread::spawn(move || accept(listener, path, count, sender));
+        Ok(SocketLines { receiver, configs })
+    }
+}
+
+impl Iterator for SocketLines {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // 所有连接都已关闭且不再接受新连接时，发送端全部释放，接收结束
+            match self.receiver.recv().ok()? {
+                Ok(line) => return Some(line),
+                Err(err) => {
+                    if !skip_err(self.configs) {
+                        err.termination()
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn bind_listener(path: &str) -> io::Result<UnixListener> {
+    match UnixListener::bind(path) {
+        Err(err) if err.kind() == io::ErrorKind::AddrInUse && is_stale_socket(path) => {
+            std::fs::remove_file(path)?;
+            UnixListener::bind(path)
+        }
+        result => result,
+    }
+}
+
+/// 套接字文件存在但没有进程在监听，通常是之前的进程异常退出后残留的。
+fn is_stale_socket(path: &str) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_socket()) && UnixStream::connect(path).is_err()
+}
+
+/// 接受连接，每个连接在单独的线程中读取，接受完指定数量的连接后删除套接字文件。
+fn accept(listener: UnixListener, path: String, count: Option<usize>, sender: SyncSender<Result<String, RpErr>>) {
+    for stream in listener.incoming().take(count.unwrap_or(usize::MAX)) {
+        let sent = match stream {
+            Ok(stream) => {
+                let (path, sender) = (path.clone(), sender.clone());
+                thread::spawn(move || read_lines(stream, path, sender));
+                true
+            }
+            Err(err) => sender.send(Err(RpErr::SocketErr { path: path.clone(), err: err.to_string() })).is_ok(),
+        };
+        if !sent {
+            break;
+        }
+    }
+    drop(listener);
+    let _ = std::fs::remove_file(&path);
+}
+
+fn read_lines(stream: UnixStream, path: String, sender: SyncSender<Result<String, RpErr>>) {
+    for line in BufReader::new(stream).lines() {
+        let item = line.map_err(|err| RpErr::SocketErr { path: path.clone(), err: err.to_string() });
+        let failed = item.is_err();
+        if sender.send(item).is_err() || failed {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn socket_path(name: &str) -> String {
+        let path: PathBuf = std::env::temp_dir().join(format!("rp_socket_{}_{name}.sock", std::process::id()));
+        path.to_string_lossy().into_owned()
+    }
+
+    fn send(path: &str, content: &str) {
+        let mut stream = UnixStream::connect(path).unwrap();
+        stream.write_all(content.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn test_socket_lines() {
+        let path = socket_path("lines");
+        let lines = SocketLines::bind(path.clone(), Some(2), &[]).unwrap();
+        send(&path, "a\nb\n");
+        send(&path, "c");
+        let mut lines = lines.collect::<Vec<_>>();
+        lines.sort();
+        assert_eq!(vec!["a", "b", "c"], lines);
+        assert!(!std::path::Path::new(&path).exists());
+    }
+
+    #[test]
+    fn test_socket_backpressure() {
+        let path = socket_path("backpressure");
+        let lines = SocketLines::bind(path.clone(), Some(1), &[]).unwrap();
+        // 超出通道容量时发送端阻塞，数据不会丢失
+        let content = (0..SOCKET_CHANNEL_CAPACITY * 3).map(|i| format!("{i}\n")).collect::<String>();
+        let sender = thread::spawn(move || send(&path, &content));
+        assert_eq!(SOCKET_CHANNEL_CAPACITY * 3, lines.count());
+        sender.join().unwrap();
+    }
+
+    #[test]
+    fn test_socket_stale() {
+        let path = socket_path("stale");
+        drop(UnixListener::bind(&path).unwrap());
+        let lines = SocketLines::bind(path.clone(), Some(1), &[]).unwrap();
+        send(&path, "a\n");
+        assert_eq!(vec!["a"], lines.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_socket_in_use() {
+        let path = socket_path("in_use");
+        let _listener = UnixListener::bind(&path).unwrap();
+        assert!(matches!(SocketLines::bind(path.clone(), Some(1), &[]), Err(RpErr::SocketErr { .. })));
+        std::fs::remove_file(path).unwrap();
+    }
+}
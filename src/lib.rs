@@ -1,9 +1,12 @@
 use crate::config::Config;
 use crate::err::RpErr;
+use crate::input::Input;
 use crate::pipe::Pipe;
 use std::iter::Peekable;
 use std::str::FromStr;
 
+#[cfg(any(windows, target_os = "linux"))]
+mod clip;
 mod condition;
 mod config;
 mod err;
@@ -15,6 +18,7 @@ mod output;
 mod parse;
 mod pipe;
 pub(crate) mod print;
+mod stdout;
 
 pub(crate) type Integer = i64;
 pub(crate) type Float = f64;
@@ -112,6 +116,14 @@ pub fn run(mut args: Peekable<impl Iterator<Item = String>>) -> Result<(), RpErr
     }
     let (input, ops, output) =
         if configs.contains(&Config::Token) { config::parse_eval_token(&mut args)? } else { parse::args::parse(args)? };
+    if input.is_follow()
+        && let Some(cmd) = ops.iter().find_map(|op| op.buffered_cmd()).or_else(|| output.buffered_cmd())
+    {
+        return Err(RpErr::BufferedOpOnFollowErr { cmd });
+    }
+    if output.is_in_place() && !matches!(input, Input::File { follow: false, .. }) {
+        return Err(RpErr::InPlaceInputErr);
+    }
     if configs.contains(&Config::Verbose) {
         config::print_pipe_info(&configs, &input, &ops, &output);
     }
@@ -119,9 +131,26 @@ pub fn run(mut args: Peekable<impl Iterator<Item = String>>) -> Result<(), RpErr
         return Ok(());
     };
     let configs: &'static mut [Config] = configs.leak();
+    stdout::set_line_flush(input.is_streaming());
+    if output.is_in_place()
+        && let Input::File { files, .. } = input
+    {
+        // 每个文件单独执行流水线，避免`:sort`、`:uniq`等操作跨文件处理数据
+        for file in files {
+            let mut pipe = Input::new_file(vec![file.clone()], false).try_into(configs)?;
+            for op in ops.iter().cloned() {
+                pipe = op.wrap(pipe, configs)?;
+            }
+            output.handle_in_place(&file, pipe)?;
+            op::tee::take_tee_err()?;
+        }
+        return stdout::flush();
+    }
     let mut pipe = input.try_into(configs)?;
     for op in ops {
         pipe = op.wrap(pipe, configs)?;
     }
-    output.handle(pipe)
+    output.handle(pipe)?;
+    op::tee::take_tee_err()?;
+    stdout::flush()
 }
@@ -0,0 +1,172 @@
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use serde_json::Value;
+
+/// 数据无法解析为JSON时的处理策略。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum JsonPolicy {
+    /// 丢弃该数据。
+    Drop,
+    /// 原样保留该数据。
+    Keep,
+    /// 报错，默认策略。
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum JsonSegment {
+    /// 对象的字段。
+    Key(String),
+    /// 数组的索引，负数表示从末尾开始计数。
+    Index(isize),
+}
+
+/// 从JSON中提取值的路径，例如`.req.user.id`、`.items[0]`、`.["a.b"][-1]`，`.`表示整个JSON。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct JsonPath {
+    segments: Vec<JsonSegment>,
+}
+
+impl JsonPath {
+    pub(crate) fn parse(path: &str) -> Result<Self, RpErr> {
+        let err = |error: &str| RpErr::ArgParseErr {
+            cmd: ":json",
+            arg: "path",
+            arg_value: path.to_string(),
+            error: error.to_string(),
+        };
+        if path == "." {
+            return Ok(JsonPath { segments: vec![] });
+        }
+        if path.is_empty() {
+            return Err(err("path must not be empty"));
+        }
+        let mut segments = vec![];
+        let mut rest = path;
+        while !rest.is_empty() {
+            if let Some(key) = rest.strip_prefix('.') {
+                if key.starts_with('[') {
+                    // `.["key"]`、`.[0]`与`["key"]`、`[0]`等价
+                    rest = key;
+                    continue;
+                }
+                let end = key.find(['.', '[']).unwrap_or(key.len());
+                if end == 0 {
+                    return Err(err("field name must not be empty"));
+                }
+                segments.push(JsonSegment::Key(key[..end].to_string()));
+                rest = &key[end..];
+            } else if let Some(inner) = rest.strip_prefix('[') {
+                let (segment, remaining) = if inner.starts_with('"') {
+                    // 带引号的字段名按照JSON字符串解析，支持转义
+                    let mut stream = serde_json::Deserializer::from_str(inner).into_iter::<String>();
+                    let key = match stream.next() {
+                        Some(Ok(key)) => key,
+                        _ => return Err(err("invalid quoted field name")),
+                    };
+                    (JsonSegment::Key(key), &inner[stream.byte_offset()..])
+                } else {
+                    let end = inner.find(']').ok_or_else(|| err("missing `]`"))?;
+                    let index = inner[..end].trim().parse().map_err(|_| err("index must be an integer"))?;
+                    (JsonSegment::Index(index), &inner[end..])
+                };
+                segments.push(segment);
+                rest = remaining.strip_prefix(']').ok_or_else(|| err("missing `]`"))?;
+            } else {
+                return Err(err("path must start with `.` or `[`"));
+            }
+        }
+        Ok(JsonPath { segments })
+    }
+
+    fn select<'a>(&self, mut value: &'a Value) -> Option<&'a Value> {
+        for segment in &self.segments {
+            value = match (segment, value) {
+                (JsonSegment::Key(key), Value::Object(map)) => map.get(key)?,
+                (JsonSegment::Index(index), Value::Array(array)) => {
+                    let index =
+                        if *index < 0 { array.len().checked_sub(index.unsigned_abs())? } else { *index as usize };
+                    array.get(index)?
+                }
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+
+    /// 将数据解析为JSON并提取路径对应的值：字符串输出原文，对象和数组输出紧凑的JSON，路径不存在时输出`null`；
+    /// 解析失败时按照策略处理，返回`None`表示丢弃该数据。
+    pub(crate) fn extract(&self, item: String, policy: &JsonPolicy, configs: &'static [Config]) -> Option<String> {
+        let json = match serde_json::from_str::<Value>(&item) {
+            Ok(json) => json,
+            Err(err) => {
+                return match policy {
+                    JsonPolicy::Drop => None,
+                    JsonPolicy::Keep => Some(item),
+                    JsonPolicy::Error => {
+                        if skip_err(configs) {
+                            None
+                        } else {
+                            RpErr::ParseJsonErr { item, err: err.to_string() }.termination()
+                        }
+                    }
+                };
+            }
+        };
+        Some(match self.select(&json) {
+            Some(Value::String(string)) => string.clone(),
+            Some(value) => value.to_string(),
+            None => Value::Null.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(path: &str, item: &str, policy: JsonPolicy) -> Option<String> {
+        JsonPath::parse(path).unwrap().extract(item.to_string(), &policy, &[])
+    }
+
+    #[test]
+    fn test_parse_path() {
+        use JsonSegment::*;
+        assert_eq!(Ok(vec![]), JsonPath::parse(".").map(|path| path.segments));
+        assert_eq!(
+            Ok(vec![Key("req".to_string()), Key("user".to_string()), Key("id".to_string())]),
+            JsonPath::parse(".req.user.id").map(|path| path.segments)
+        );
+        assert_eq!(
+            Ok(vec![Key("items".to_string()), Index(0), Index(-1)]),
+            JsonPath::parse(".items[0][-1]").map(|path| path.segments)
+        );
+        assert_eq!(
+            Ok(vec![Key("a.b".to_string()), Key("c\"]".to_string()), Index(2)]),
+            JsonPath::parse(r#".["a.b"]["c\"]"].[2]"#).map(|path| path.segments)
+        );
+        for path in ["", "a", "..a", ".a.", ".a[", ".a[x]", ".a[\"b\"", ".a[0]b"] {
+            assert!(JsonPath::parse(path).is_err(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_extract() {
+        let item = r#"{"req":{"user":{"id":7,"name":"Jack"}},"items":[{"a":1},[true,null]],"s":"x"}"#;
+        assert_eq!(Some("7".to_string()), extract(".req.user.id", item, JsonPolicy::Error));
+        assert_eq!(Some("Jack".to_string()), extract(".req.user.name", item, JsonPolicy::Error));
+        assert_eq!(Some(r#"{"id":7,"name":"Jack"}"#.to_string()), extract(".req.user", item, JsonPolicy::Error));
+        assert_eq!(Some(r#"{"a":1}"#.to_string()), extract(".items[0]", item, JsonPolicy::Error));
+        assert_eq!(Some("true".to_string()), extract(".items[-1][0]", item, JsonPolicy::Error));
+        assert_eq!(Some("null".to_string()), extract(".items[5]", item, JsonPolicy::Error));
+        assert_eq!(Some("null".to_string()), extract(".s.x", item, JsonPolicy::Error));
+        assert_eq!(Some("x".to_string()), extract(".", r#" "x" "#, JsonPolicy::Error));
+        assert_eq!(Some("[1,2]".to_string()), extract(".", "[1, 2]", JsonPolicy::Error));
+    }
+
+    #[test]
+    fn test_extract_invalid() {
+        assert_eq!(None, extract(".a", "{a:1}", JsonPolicy::Drop));
+        assert_eq!(Some("{a:1}".to_string()), extract(".a", "{a:1}", JsonPolicy::Keep));
+    }
+}
@@ -1,15 +1,25 @@
+pub(crate) mod json;
 mod replace;
 mod slice;
+pub(crate) mod tee;
 pub(crate) mod trim;
+pub(crate) mod zip;
 
 use crate::condition::Condition;
 use crate::config::{Config, is_nocase};
 use crate::err::RpErr;
 use crate::fmt::{FmtArg, fmt_args};
+use crate::op::json::{JsonPath, JsonPolicy};
 use crate::op::replace::ReplaceArg;
 use crate::op::slice::SliceIter;
+use crate::op::tee::TeeIter;
 use crate::op::trim::TrimArg;
-use crate::pipe::Pipe;
+use crate::op::zip::{ZipIter, ZipJoin, ZipPolicy};
+use crate::output::Output;
+use crate::output::compress::{Compression, open_file};
+use crate::output::line_ending::LineEnding;
+use crate::pipe::{MetaCell, Pipe, fmt_with_meta};
+use crate::stdout;
 use crate::{Float, Integer, Num, PipeRes};
 use cmd_help::CmdHelp;
 use itertools::Itertools;
@@ -19,11 +29,10 @@ use regex::Regex;
 use rustc_hash::FxHashSet;
 use std::borrow::Cow;
 use std::cmp::Reverse;
-use std::fs::OpenOptions;
 use std::io::Write;
 use unicase::UniCase;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct RegArg {
     regex: Regex,
     count: Option<usize>,
@@ -54,14 +63,16 @@ impl PartialEq for RegArg {
     }
 }
 
-#[derive(Debug, PartialEq, CmdHelp)]
+#[derive(Debug, Clone, PartialEq, CmdHelp)]
 pub(crate) enum Op {
     /* **************************************** 访问 **************************************** */
     /// :peek       打印每个值到标准输出或文件。
-    ///             :peek[ <file>][ append][ lf|crlf]
-    ///                 <file>  文件路径，可选。
-    ///                 append  追加输出而不是覆盖，可选，如果未指定则覆盖源文件。
-    ///                 lf|crlf 指定换行符为'LF'或'CRLF'，可选，如果未指定则默认使用'LF'。
+    ///             :peek[ <file>[ append][ <ending>][ gzip|zstd[ <level>]]][ fmt <fmt>]
+    ///                 <file>      文件路径，可选，如果未指定则打印到标准输出。
+    ///                 append      追加输出而不是覆盖，可选，如果未指定则覆盖源文件。
+    ///                 <ending>    每个值之后的结束符，可选，参数同`:to file`。
+    ///                 gzip|zstd   压缩格式，可选，参数同`:to file`。
+    ///                 <fmt>       打印时使用的格式化字符串，可选，参数同`:fmt`。
     ///             例如：
     ///                 :peek
     ///                 :peek file.txt
@@ -69,7 +80,20 @@ pub(crate) enum Op {
     ///                 :peek file.txt lf
     ///                 :peek file.txt crlf
     ///                 :peek file.txt append crlf
-    Peek(PeekArg),
+    ///                 :peek file.txt nul
+    ///                 :peek file.txt.gz
+    ///                 :peek fmt "{file}:{line}: {v}"
+    Peek { arg: PeekArg, fmt: Option<String> },
+    /// :tee        将每个值复制一份交给指定的输出，原值继续传递给后续命令，类似`tee`。
+    ///             :tee <output>
+    ///                 <output>    输出命令，必选，支持除`:to inplace`外的所有输出命令，参考`-h out`。
+    ///             输出在后台执行，与最终输出同时写入标准输出时，两者的数据顺序不确定。
+    ///             后续命令提前结束读取（例如`:limit`）时，只复制已经读取的值。
+    ///             例如：
+    ///                 :tee :to file all.txt
+    ///                 :tee :to file all.txt :take /error/ :tee :to clip :to out
+    ///                 :tee :to split "part_{n}.txt" lines 1000
+    Tee { output: Output },
     /* **************************************** 转换 **************************************** */
     /// :upper      转为ASCII大写。
     /// :lower      转为ASCII小写。
@@ -133,6 +157,43 @@ pub(crate) enum Op {
     ///                 :reg '\d' 3         // 最多匹配3次，"1a23" -> "123"
     ///                 :reg '\d' 2         // 最多匹配2次，"1a23" -> "12"
     Reg(RegArg),
+    /// :fmt        格式化每个数据。
+    ///             :fmt <fmt>
+    ///                 <fmt>   格式化字符串，必选，支持以下参数：
+    ///                             {v}     当前数据。
+    ///                             {file}  数据的来源文件，仅`:file`和`:csv`可用，否则为空。
+    ///                                     `:csv`从标准输入读取时为'-'。
+    ///                             {line}  数据在来源文件中的行号，从1开始，可用范围同{file}。
+    ///                                     跟踪文件时，文件被截断或轮转后重新从1开始。
+    ///                             {0}     数据的字段，按照位置引用，仅`:csv`可用。
+    ///                             {name}  数据的字段，按照`:csv`表头中的名称引用，与上述参数同名时只能按照位置引用。
+    ///                         经过缓存、重排或合并数据的操作，例如`:sort`、`:join`、`:zip`、`:interleave`后，
+    ///                         来源信息不再可用。
+    ///                         更多格式化信息参考`-h fmt`。
+    ///             例如：
+    ///                 :fmt "[{v}]"
+    ///                 :fmt "{line:>4} {v}"
+    ///                 :take reg '.*TODO.*' :fmt "{file}:{line}:{v}"
+    ///                 :csv users.csv header :fmt "{name}: {1}"
+    Fmt { fmt: String },
+    /// :json       将每个数据解析为JSON，并提取指定路径的值，适用于JSON Lines格式的数据。
+    ///             :json <path>[ drop|keep|error]
+    ///                 <path>  提取值的路径，必选，由以下部分组成：
+    ///                             .<name>     对象的字段，字段名不能包含'.'和'['。
+    ///                             ["<name>"]  对象的字段，字段名按照JSON字符串解析，可以包含任意字符。
+    ///                             [<index>]   数组的元素，从0开始，负数表示从末尾开始计数。
+    ///                         单独的'.'表示整个JSON。
+    ///                         提取的值为字符串时输出字符串内容，为对象或数组时输出紧凑的JSON，
+    ///                         路径不存在时输出null。
+    ///                 drop    丢弃无法解析为JSON的数据。
+    ///                 keep    原样保留无法解析为JSON的数据。
+    ///                 error   无法解析为JSON时报错，默认策略。
+    ///             例如：
+    ///                 :json .req.user.id
+    ///                 :json .items[0] drop
+    ///                 :json '.["a.b"][-1]' keep
+    ///                 :json .
+    Json { path: JsonPath, policy: JsonPolicy },
     /* **************************************** 减少 **************************************** */
     /// :limit      保留前N个数据，丢弃后续的其他数据。
     ///             :limit <count>
@@ -197,6 +258,33 @@ pub(crate) enum Op {
     ///             :count
     Count,
     /* **************************************** 增加 **************************************** */
+    /// :zip        将每个数据与文件中相同位置的行合并为一个数据，类似`paste`。
+    ///             :zip <file>[ <delimiter>|fmt <fmt>][ stop|pad[ <fill>]|error]
+    ///                 <file>      文件路径，必选。
+    ///                 <delimiter> 分隔字符串，可选，未指定时使用制表符。
+    ///                 <fmt>       格式化字符串，以{0}表示上游数据，以{1}表示文件中的行，可选。
+    ///                             更多格式化信息参考`-h fmt`。
+    ///                 stop        任意一方数据结束时停止，默认策略。
+    ///                 pad         使用<fill>补齐数据较少的一方，直到双方数据都结束。
+    ///                     <fill>  填充值，可选，未指定时使用空字符串。
+    ///                 error       双方数据数量不一致时报错。
+    ///             例如：
+    ///                 :zip names.txt
+    ///                 :zip names.txt ,
+    ///                 :zip names.txt fmt "{0}={1}" pad NULL
+    ///                 :zip names.txt , error
+    Zip { file: String, join: ZipJoin, policy: ZipPolicy },
+    /// :interleave 交替输出上游数据和文件中的行。
+    ///             :interleave <file>[ stop|pad[ <fill>]|error]
+    ///                 <file>      文件路径，必选。
+    ///                 stop        任意一方数据结束时停止，默认策略。
+    ///                 pad         使用<fill>补齐数据较少的一方，直到双方数据都结束。
+    ///                     <fill>  填充值，可选，未指定时使用空字符串。
+    ///                 error       双方数据数量不一致时报错。
+    ///             例如：
+    ///                 :interleave answers.txt
+    ///                 :interleave answers.txt pad -
+    Interleave { file: String, policy: ZipPolicy },
     /* **************************************** 调整位置 **************************************** */
     /// :sort       排序。
     ///             :sort[ num [<default>]][ nocase][ desc][ random]
@@ -223,6 +311,23 @@ pub(crate) enum Op {
 }
 
 impl Op {
+    pub(crate) fn new_peek(arg: PeekArg, fmt: Option<String>) -> Op {
+        Op::Peek { arg, fmt }
+    }
+    pub(crate) fn new_tee(output: Output) -> Result<Op, RpErr> {
+        if output.is_in_place() {
+            return Err(RpErr::ArgParseErr {
+                cmd: ":tee",
+                arg: "output",
+                arg_value: ":to inplace".to_string(),
+                error: "in-place output cannot be used as a tee target".to_string(),
+            });
+        }
+        Ok(Op::Tee { output })
+    }
+    pub(crate) fn new_json(path: JsonPath, policy: JsonPolicy) -> Op {
+        Op::Json { path, policy }
+    }
     pub(crate) fn new_replace(from: String, to: String, count: Option<usize>, nocase: bool) -> Op {
         Op::Replace(ReplaceArg::new(from, to, count, nocase))
     }
@@ -235,17 +340,44 @@ impl Op {
     pub(crate) fn new_sort(sort_by: SortBy, desc: bool) -> Op {
         Op::Sort { sort_by, desc }
     }
+    pub(crate) fn new_zip(file: String, join: ZipJoin, policy: ZipPolicy) -> Op {
+        Op::Zip { file, join, policy }
+    }
+    pub(crate) fn new_interleave(file: String, policy: ZipPolicy) -> Op {
+        Op::Interleave { file, policy }
+    }
+
+    /// 如果需要缓存全部上游数据后才能产生结果，则返回命令名称，此类操作无法用于永不结束的输入。
+    pub(crate) fn buffered_cmd(&self) -> Option<&'static str> {
+        match self {
+            Op::Sum { .. } => Some(":sum"),
+            Op::Join { batch: None, .. } => Some(":join"),
+            Op::Count => Some(":count"),
+            Op::Sort { .. } => Some(":sort"),
+            Op::Tee { output } => output.buffered_cmd(),
+            _ => None,
+        }
+    }
 
     pub(crate) fn wrap(self, mut pipe: Pipe, configs: &'static [Config]) -> PipeRes {
         match self {
-            Op::Peek(peek) => match peek {
-                PeekArg::StdOut => Ok(pipe.op_inspect(|item| println!("{item}"))),
-                PeekArg::File { file, append, crlf } => {
-                    match OpenOptions::new().write(true).truncate(!append).append(append).create(true).open(&file) {
+            Op::Peek { arg, fmt } => match arg {
+                PeekArg::StdOut => {
+                    let meta = pipe.meta.clone();
+                    Ok(pipe.op_inspect(move |item| {
+                        if let Err(err) = stdout::write_line(&fmt_item(item, fmt.as_deref(), meta.as_ref())) {
+                            err.termination()
+                        }
+                    }))
+                }
+                PeekArg::File { file, append, ending, compression } => {
+                    // 写入器随管道一起析构或直接退出进程时写入压缩流的结尾
+                    match open_file(&file, append, compression) {
                         Ok(mut writer) => {
-                            let postfix = if crlf.unwrap_or(false) { "\r\n" } else { "\n" };
+                            let meta = pipe.meta.clone();
                             Ok(pipe.op_inspect(move |item| {
-                                if let Err(err) = write!(writer, "{item}{postfix}") {
+                                let text = fmt_item(item, fmt.as_deref(), meta.as_ref());
+                                if let Err(err) = write!(writer, "{text}{}", ending.as_str()) {
                                     RpErr::WriteToFileErr {
                                         file: file.clone(),
                                         item: item.to_string(),
@@ -259,6 +391,10 @@ impl Op {
                     }
                 }
             },
+            Op::Tee { output } => {
+                let meta = pipe.meta.clone();
+                Ok(Pipe { iter: Box::new(TeeIter::new(pipe, output, configs)), meta })
+            }
             Op::Case(case_arg) => match case_arg {
                 CaseArg::Lower => Ok(pipe.op_map(|mut item|
                     // OPT 2026-12-29 01:24 Pipe增加属性以优化重复大小写。
@@ -306,8 +442,19 @@ impl Op {
             }
             Op::Trim(trim_arg) => Ok(pipe.op_map(move |s| trim_arg.trim(s, configs))),
             Op::Reg(reg_arg) => Ok(pipe.op_map(move |s| reg_arg.replace(&s))),
+            Op::Fmt { fmt } => {
+                let meta = pipe.meta.clone();
+                Ok(pipe.op_map(move |s| fmt_item(&s, Some(&fmt), meta.as_ref()).into_owned()))
+            }
+            Op::Json { path, policy } => {
+                let meta = pipe.meta.clone();
+                Ok(Pipe { iter: Box::new(pipe.filter_map(move |item| path.extract(item, &policy, configs))), meta })
+            }
             // OPT 2026-01-22 01:10 针对 limit 0、skip 0 等命令进行优化
-            Op::Slice { ranges } => Ok(Pipe { iter: Box::new(SliceIter::new(pipe, ranges)) }),
+            Op::Slice { ranges } => {
+                let meta = pipe.meta.clone();
+                Ok(Pipe { iter: Box::new(SliceIter::new(pipe, ranges)), meta })
+            }
             Op::Uniq { nocase } => {
                 let mut seen = FxHashSet::default();
                 Ok(pipe.op_filter(move |item| {
@@ -336,12 +483,15 @@ impl Op {
                         }
                     }
                 };
-                Ok(Pipe { iter: Box::new(std::iter::once(out)) })
+                Ok(Pipe { iter: Box::new(std::iter::once(out)), meta: None })
             }
             Op::Join { join_info, batch: count } => {
                 if let Some(count) = count {
                     if count > 0 {
-                        return Ok(Pipe { iter: Box::new(ChunkJoin { source: pipe, group_size: count, join_info }) });
+                        return Ok(Pipe {
+                            iter: Box::new(ChunkJoin { source: pipe, group_size: count, join_info }),
+                            meta: None,
+                        });
                     } else {
                         unreachable!("join count must be greater than zero");
                     }
@@ -353,15 +503,30 @@ impl Op {
                         pipe.join(&join_info.delimiter),
                         join_info.postfix
                     ))),
+                    meta: None,
                 })
             }
-            Op::TakeDrop { mode, cond } => match mode {
-                TakeDropMode::Take => Ok(Pipe { iter: Box::new(pipe.filter(move |s| cond.test(s))) }),
-                TakeDropMode::Drop => Ok(Pipe { iter: Box::new(pipe.filter(move |s| !cond.test(s))) }),
-                TakeDropMode::TakeWhile => Ok(Pipe { iter: Box::new(pipe.take_while(move |s| cond.test(s))) }),
-                TakeDropMode::DropWhile => Ok(Pipe { iter: Box::new(pipe.skip_while(move |s| cond.test(s))) }),
-            },
-            Op::Count => Ok(Pipe { iter: Box::new(std::iter::once(pipe.count().to_string())) }),
+            Op::TakeDrop { mode, cond } => {
+                let meta = pipe.meta.clone();
+                match mode {
+                    TakeDropMode::Take => Ok(Pipe { iter: Box::new(pipe.filter(move |s| cond.test(s))), meta }),
+                    TakeDropMode::Drop => Ok(Pipe { iter: Box::new(pipe.filter(move |s| !cond.test(s))), meta }),
+                    TakeDropMode::TakeWhile => {
+                        Ok(Pipe { iter: Box::new(pipe.take_while(move |s| cond.test(s))), meta })
+                    }
+                    TakeDropMode::DropWhile => {
+                        Ok(Pipe { iter: Box::new(pipe.skip_while(move |s| cond.test(s))), meta })
+                    }
+                }
+            }
+            Op::Count => Ok(Pipe { iter: Box::new(std::iter::once(pipe.count().to_string())), meta: None }),
+            // 补齐时上游已经结束，来源信息不再对应当前数据
+            Op::Zip { file, join, policy } => {
+                Ok(Pipe { iter: Box::new(ZipIter::new(pipe, file, Some(join), policy, configs)?), meta: None })
+            }
+            Op::Interleave { file, policy } => {
+                Ok(Pipe { iter: Box::new(ZipIter::new(pipe, file, None, policy, configs)?), meta: None })
+            }
             Op::Sort { sort_by, desc } => match sort_by {
                 SortBy::Num(def_integer, def_float) => {
                     if let Some(def) = def_integer {
@@ -371,7 +536,7 @@ impl Op {
                         } else {
                             pipe.sorted_by_key(key_fn)
                         };
-                        return Ok(Pipe { iter: Box::new(new_pipe) });
+                        return Ok(Pipe { iter: Box::new(new_pipe), meta: None });
                     }
                     let def = def_float.unwrap_or(Float::MAX); // 默认按照浮点最大值
                     let key_fn = move |item: &String| OrderedFloat(item.parse().unwrap_or(def));
@@ -380,7 +545,7 @@ impl Op {
                     } else {
                         pipe.sorted_by_key(key_fn)
                     };
-                    Ok(Pipe { iter: Box::new(new_pipe) })
+                    Ok(Pipe { iter: Box::new(new_pipe), meta: None })
                 }
                 SortBy::Text(nocase) => {
                     // TODO 2026-01-08 02:34 使用UniCase优化其他nocase场景
@@ -395,39 +560,50 @@ impl Op {
                     } else {
                         pipe.sorted_by_key(|item| item.to_string())
                     };
-                    Ok(Pipe { iter: Box::new(iter) })
+                    Ok(Pipe { iter: Box::new(iter), meta: None })
                 }
                 SortBy::Random => {
                     let mut v = pipe.collect::<Vec<_>>();
                     v.shuffle(&mut rand::rng());
-                    Ok(Pipe { iter: Box::new(v.into_iter()) })
+                    Ok(Pipe { iter: Box::new(v.into_iter()), meta: None })
                 }
             },
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// 使用格式化字符串格式化数据，未指定格式化字符串时返回原数据。
+fn fmt_item<'a>(item: &'a str, fmt: Option<&str>, meta: Option<&MetaCell>) -> Cow<'a, str> {
+    match fmt {
+        Some(fmt) => match fmt_with_meta(fmt, item, meta) {
+            Ok(string) => Cow::Owned(string),
+            Err(err) => err.termination(),
+        },
+        None => Cow::Borrowed(item),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum CaseArg {
     Upper,
     Lower,
     Switch,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum PeekArg {
     StdOut,
-    File { file: String, append: bool, crlf: Option<bool> },
+    File { file: String, append: bool, ending: LineEnding, compression: Option<Compression> },
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum SortBy {
     Num(Option<Integer>, Option<Float>),
     Text(bool /*nocase*/),
     Random,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum TakeDropMode {
     Take,
     Drop,
@@ -435,7 +611,7 @@ pub(crate) enum TakeDropMode {
     DropWhile,
 }
 
-#[derive(Debug, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub(crate) struct JoinInfo {
     pub(crate) delimiter: String,
     pub(crate) prefix: String,
@@ -479,11 +655,12 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::condition::Select;
     use crate::pipe::Pipe;
 
     #[test]
     fn test_sum_without_fmt() {
-        let input = Pipe { iter: Box::new(vec!["1", "2", "3"].into_iter().map(|s| s.to_string())) };
+        let input = Pipe { iter: Box::new(vec!["1", "2", "3"].into_iter().map(|s| s.to_string())), meta: None };
         let result = Op::Sum { fmt: None }.wrap(input, &[]).unwrap();
         let output: Vec<String> = result.collect();
         assert_eq!(output, vec!["6"]);
@@ -491,7 +668,7 @@ mod tests {
 
     #[test]
     fn test_sum_with_fmt() {
-        let input = Pipe { iter: Box::new(vec!["1", "2", "3"].into_iter().map(|s| s.to_string())) };
+        let input = Pipe { iter: Box::new(vec!["1", "2", "3"].into_iter().map(|s| s.to_string())), meta: None };
         let result = Op::Sum { fmt: Some("Result: {v}".to_string()) }.wrap(input, &[]).unwrap();
         let output: Vec<String> = result.collect();
         assert_eq!(output, vec!["Result: 6"]);
@@ -499,7 +676,7 @@ mod tests {
 
     #[test]
     fn test_sum_with_custom_fmt() {
-        let input = Pipe { iter: Box::new(vec!["10", "20", "30"].into_iter().map(|s| s.to_string())) };
+        let input = Pipe { iter: Box::new(vec!["10", "20", "30"].into_iter().map(|s| s.to_string())), meta: None };
         let result = Op::Sum { fmt: Some("Total: {v}".to_string()) }.wrap(input, &[]).unwrap();
         let output: Vec<String> = result.collect();
         assert_eq!(output, vec!["Total: 60"]);
@@ -507,7 +684,7 @@ mod tests {
 
     #[test]
     fn test_sum_with_hex_fmt() {
-        let input = Pipe { iter: Box::new(vec!["10", "20", "30"].into_iter().map(|s| s.to_string())) };
+        let input = Pipe { iter: Box::new(vec!["10", "20", "30"].into_iter().map(|s| s.to_string())), meta: None };
         let result = Op::Sum { fmt: Some("Sum = {v}".to_string()) }.wrap(input, &[]).unwrap();
         let output: Vec<String> = result.collect();
         assert_eq!(output, vec!["Sum = 60"]);
@@ -515,7 +692,7 @@ mod tests {
 
     #[test]
     fn test_sum_with_float_input() {
-        let input = Pipe { iter: Box::new(vec!["1.5", "2.5", "3.0"].into_iter().map(|s| s.to_string())) };
+        let input = Pipe { iter: Box::new(vec!["1.5", "2.5", "3.0"].into_iter().map(|s| s.to_string())), meta: None };
         let result = Op::Sum { fmt: Some("{v}".to_string()) }.wrap(input, &[]).unwrap();
         let output: Vec<String> = result.collect();
         assert_eq!(output, vec!["7"]);
@@ -523,12 +700,79 @@ mod tests {
 
     #[test]
     fn test_sum_with_mixed_input() {
-        let input = Pipe { iter: Box::new(vec!["1", "2.5", "abc", "3"].into_iter().map(|s| s.to_string())) };
+        let input =
+            Pipe { iter: Box::new(vec!["1", "2.5", "abc", "3"].into_iter().map(|s| s.to_string())), meta: None };
         let result = Op::Sum { fmt: None }.wrap(input, &[]).unwrap();
         let output: Vec<String> = result.collect();
         assert_eq!(output, vec!["6.5"]);
     }
 
+    fn meta_pipe(items: &[&str]) -> Pipe {
+        let meta = MetaCell::default();
+        let writer = meta.clone();
+        let file = std::rc::Rc::new("a.txt".to_string());
+        let items = items.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let iter = items.into_iter().enumerate().map(move |(idx, item)| {
+            *writer.borrow_mut() = Some(crate::pipe::ItemMeta { file: file.clone(), line: idx + 1, fields: None });
+            item
+        });
+        Pipe { iter: Box::new(iter), meta: Some(meta) }
+    }
+
+    #[test]
+    fn test_fmt_with_meta() {
+        let pipe = Op::new_take_drop(TakeDropMode::Drop, Condition::new(Select::Num { integer: None }, false))
+            .wrap(meta_pipe(&["x", "1", "y"]), &[])
+            .unwrap();
+        let pipe = Op::Fmt { fmt: "{file}:{line}:{v}".to_string() }.wrap(pipe, &[]).unwrap();
+        assert_eq!(vec!["a.txt:1:x", "a.txt:3:y"], pipe.collect::<Vec<_>>());
+
+        let pipe = Op::new_sort(SortBy::Text(false), false).wrap(meta_pipe(&["b", "a"]), &[]).unwrap();
+        assert!(pipe.meta.is_none());
+        let pipe = Op::Fmt { fmt: "[{file}{line}]{v}".to_string() }.wrap(pipe, &[]).unwrap();
+        assert_eq!(vec!["[]a", "[]b"], pipe.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_meta_propagation() {
+        // 逐个过滤的操作保留来源信息
+        let pipe = Op::Uniq { nocase: false }.wrap(meta_pipe(&["a", "a", "b"]), &[]).unwrap();
+        let pipe = Op::Fmt { fmt: "{line}:{v}".to_string() }.wrap(pipe, &[]).unwrap();
+        assert_eq!(vec!["1:a", "3:b"], pipe.collect::<Vec<_>>());
+
+        // 缓存、重排或合并数据的操作清除来源信息
+        let file = std::env::temp_dir().join(format!("rp_op_meta_{}.txt", std::process::id()));
+        std::fs::write(&file, "x\n").unwrap();
+        let file = file.to_string_lossy().into_owned();
+        let ops = vec![
+            Op::new_sort(SortBy::Random, false),
+            Op::new_join(JoinInfo::default(), Some(2)),
+            Op::Count,
+            Op::new_zip(file.clone(), ZipJoin::Delimiter(",".to_string()), ZipPolicy::Pad(String::new())),
+            Op::new_interleave(file.clone(), ZipPolicy::Stop),
+        ];
+        for op in ops {
+            assert!(op.wrap(meta_pipe(&["a", "b"]), &[]).unwrap().meta.is_none());
+        }
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn test_buffered_cmd() {
+        assert_eq!(Some(":sort"), Op::new_sort(SortBy::Random, false).buffered_cmd());
+        assert_eq!(Some(":count"), Op::Count.buffered_cmd());
+        assert_eq!(Some(":sum"), Op::Sum { fmt: None }.buffered_cmd());
+        assert_eq!(Some(":join"), Op::new_join(JoinInfo::default(), None).buffered_cmd());
+        assert_eq!(None, Op::new_join(JoinInfo::default(), Some(2)).buffered_cmd());
+        assert_eq!(None, Op::new_peek(PeekArg::StdOut, None).buffered_cmd());
+        assert_eq!(None, Op::Uniq { nocase: false }.buffered_cmd());
+        let file = |atomic| Output::new_file("out.txt".to_string(), false, atomic, LineEnding::default(), None);
+        assert_eq!(None, Op::new_tee(file(false)).unwrap().buffered_cmd());
+        assert_eq!(Some(":to file"), Op::new_tee(file(true)).unwrap().buffered_cmd());
+        assert_eq!(Some(":to json"), Op::new_tee(Output::new_json(None, false)).unwrap().buffered_cmd());
+        assert_eq!(None, Op::new_tee(Output::new_nd_json(None, false)).unwrap().buffered_cmd());
+    }
+
     #[test]
     fn test_reg_basic_match() {
         let reg_arg = RegArg::new(r"\d+".to_string(), None).unwrap();
@@ -667,7 +911,8 @@ mod tests {
 
     #[test]
     fn test_reg_op_wrap() {
-        let input = Pipe { iter: Box::new(vec!["abc1d", "abc", "1a23"].into_iter().map(|s| s.to_string())) };
+        let input =
+            Pipe { iter: Box::new(vec!["abc1d", "abc", "1a23"].into_iter().map(|s| s.to_string())), meta: None };
         let reg_arg = RegArg::new(r"\d+".to_string(), None).unwrap();
         let result = Op::Reg(reg_arg).wrap(input, &[]).unwrap();
         let output: Vec<String> = result.collect();
@@ -676,7 +921,8 @@ mod tests {
 
     #[test]
     fn test_reg_op_wrap_with_count() {
-        let input = Pipe { iter: Box::new(vec!["1a23", "abc", "12345"].into_iter().map(|s| s.to_string())) };
+        let input =
+            Pipe { iter: Box::new(vec!["1a23", "abc", "12345"].into_iter().map(|s| s.to_string())), meta: None };
         let reg_arg = RegArg::new(r"\d".to_string(), Some(2)).unwrap();
         let result = Op::Reg(reg_arg).wrap(input, &[]).unwrap();
         let output: Vec<String> = result.collect();
@@ -1,7 +1,7 @@
 use crate::config::{Config, is_nocase};
 use std::borrow::Cow;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct ReplaceArg {
     from: String, /*nocase时需要转为小写*/
     to: String,
@@ -0,0 +1,152 @@
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use crate::output::Output;
+use crate::pipe::Pipe;
+use crate::stdout;
+use std::sync::mpsc::{SyncSender, sync_channel};
+use std::sync::{Mutex, PoisonError};
+use std::thread::JoinHandle;
+
+/// 复制数据的通道容量，输出较慢时上游最多领先此数量的数据。
+const TEE_CHANNEL_CAPACITY: usize = 1024;
+
+/// 析构时才结束的输出的第一个错误，析构时无法直接退出进程，由`take_tee_err`交给流水线的结果报告。
+static TEE_ERR: Mutex<Option<RpErr>> = Mutex::new(None);
+
+/// 取出析构时结束的`:tee`输出的错误。
+pub(crate) fn take_tee_err() -> Result<(), RpErr> {
+    match TEE_ERR.lock().unwrap_or_else(PoisonError::into_inner).take() {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// 将每个数据复制一份交给在后台线程中执行的输出，原数据继续向下游传递。
+/// 上游结束或下游不再读取时关闭通道，并等待输出完成。
+pub(in crate::op) struct TeeIter {
+    source: Pipe,
+    sender: Option<SyncSender<String>>,
+    handle: Option<JoinHandle<Result<(), RpErr>>>,
+    configs: &'static [Config],
+}
+
+impl TeeIter {
+    pub(in crate::op) fn new(source: Pipe, output: Output, configs: &'static [Config]) -> Self {
+        let (sender, receiver) = sync_channel(TEE_CHANNEL_CAPACITY);
+        // 输出线程使用单独的标准输出缓冲区，结束时需要刷新
+        let handle = std::thread::spawn(move || {
+            output.handle(Pipe { iter: Box::new(receiver.into_iter()), meta: None }).and_then(|_| stdout::flush())
+        });
+        TeeIter { source, sender: Some(sender), handle: Some(handle), configs }
+    }
+
+    /// 关闭通道并等待输出完成。
+    fn join(&mut self) -> Result<(), RpErr> {
+        // 先关闭通道，输出才能读取到结尾
+        self.sender.take();
+        match self.handle.take() {
+            Some(handle) => match handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)) {
+                Err(_) if skip_err(self.configs) => Ok(()),
+                result => result,
+            },
+            None => Ok(()),
+        }
+    }
+
+    fn finish(&mut self) {
+        if let Err(err) = self.join() {
+            err.termination()
+        }
+    }
+}
+
+impl Iterator for TeeIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.source.next() {
+            Some(item) => {
+                // 输出提前结束（例如出错后跳过）时不再复制
+                if let Some(sender) = &self.sender
+                    && sender.send(item.clone()).is_err()
+                {
+                    self.finish();
+                }
+                Some(item)
+            }
+            None => {
+                self.finish();
+                None
+            }
+        }
+    }
+}
+
+impl Drop for TeeIter {
+    fn drop(&mut self) {
+        // 展开时不再等待输出，避免再次panic
+        if std::thread::panicking() {
+            self.sender.take();
+            return;
+        }
+        if let Err(err) = self.join() {
+            TEE_ERR.lock().unwrap_or_else(PoisonError::into_inner).get_or_insert(err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::line_ending::LineEnding;
+
+    fn pipe(items: &[&str]) -> Pipe {
+        Pipe { iter: Box::new(items.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter()), meta: None }
+    }
+
+    fn temp_file(name: &str) -> String {
+        let file = std::env::temp_dir().join(format!("rp_tee_{}_{name}", std::process::id()));
+        let _ = std::fs::remove_file(&file);
+        file.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_tee() {
+        let file = temp_file("all");
+        let tee = TeeIter::new(
+            pipe(&["a", "b", "c"]),
+            Output::new_file(file.clone(), false, false, LineEnding::default(), None),
+            &[],
+        );
+        assert_eq!(vec!["a", "b", "c"], tee.collect::<Vec<_>>());
+        assert_eq!("a\nb\nc\n", std::fs::read_to_string(&file).unwrap());
+    }
+
+    #[test]
+    fn test_tee_dropped_early() {
+        let file = temp_file("partial");
+        let mut tee = TeeIter::new(
+            pipe(&["a", "b", "c"]),
+            Output::new_file(file.clone(), false, false, LineEnding::default(), None),
+            &[],
+        );
+        assert_eq!(Some("a".to_string()), tee.next());
+        drop(tee);
+        assert_eq!("a\n", std::fs::read_to_string(&file).unwrap());
+    }
+
+    #[test]
+    fn test_tee_err_on_drop() {
+        let file = std::env::temp_dir().join("rp_tee_not_exists").join("out.txt").to_string_lossy().into_owned();
+        let mut tee = TeeIter::new(
+            pipe(&["a", "b", "c"]),
+            Output::new_file(file.clone(), false, false, LineEnding::default(), None),
+            &[],
+        );
+        assert_eq!(Some("a".to_string()), tee.next());
+        // 析构时不直接退出，错误交给流水线的结果
+        drop(tee);
+        assert!(matches!(take_tee_err(), Err(RpErr::OpenFileErr { file: err_file, .. }) if err_file == file));
+        assert_eq!(Ok(()), take_tee_err());
+    }
+}
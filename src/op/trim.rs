@@ -10,7 +10,7 @@ pub(crate) enum TrimPos {
     Both,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum TrimParam {
     Blank,
     Str(String),
@@ -18,7 +18,7 @@ pub(crate) enum TrimParam {
     Regex { primary: Regex, secondary: Option<Regex> /*仅用于Both时匹配Tail*/ },
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct TrimArg {
     pos: TrimPos,
     param: TrimParam,
@@ -0,0 +1,171 @@
+use crate::config::{Config, skip_err};
+use crate::err::RpErr;
+use crate::fmt::{FmtArg, fmt_positional_args};
+use crate::pipe::Pipe;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// 未指定分隔符时与`paste`一致使用制表符。
+pub(crate) const DEFAULT_ZIP_DELIMITER: &str = "\t";
+
+/// 两个数据源数量不一致时的处理策略。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum ZipPolicy {
+    /// 任意数据源结束时停止。
+    Stop,
+    /// 使用填充值补齐较短的数据源，直到所有数据源结束。
+    Pad(String),
+    /// 数量不一致时报错。
+    Error,
+}
+
+/// 将两个数据源的同一位置的数据合并为一个数据的方式。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) enum ZipJoin {
+    /// 以分隔符连接。
+    Delimiter(String),
+    /// 以格式化字符串合并，`{0}`引用输入的数据，`{1}`引用文件的数据。
+    Fmt(String),
+}
+
+impl ZipJoin {
+    fn join(&self, left: String, right: String) -> String {
+        match self {
+            ZipJoin::Delimiter(delimiter) => left + delimiter + &right,
+            ZipJoin::Fmt(fmt) => match fmt_positional_args(fmt, &[FmtArg::from(left), FmtArg::from(right)], &[]) {
+                Ok(string) => string,
+                Err(err) => err.termination(),
+            },
+        }
+    }
+}
+
+/// 逐项合并或交替输出上游数据和另一个数据源的数据。
+pub(in crate::op) struct ZipIter {
+    cmd: &'static str,
+    main: Pipe,
+    other: Box<dyn Iterator<Item = String>>,
+    file: String,
+    /// 为`None`时交替输出。
+    join: Option<ZipJoin>,
+    policy: ZipPolicy,
+    /// 交替输出时暂存的另一个数据源的数据。
+    pending: Option<String>,
+    done: bool,
+    configs: &'static [Config],
+}
+
+impl ZipIter {
+    pub(in crate::op) fn new(
+        main: Pipe, file: String, join: Option<ZipJoin>, policy: ZipPolicy, configs: &'static [Config],
+    ) -> Result<Self, RpErr> {
+        let reader = BufReader::new(
+            File::open(&file).map_err(|err| RpErr::OpenFileErr { file: file.clone(), err: err.to_string() })?,
+        );
+        let file_name = file.clone();
+        let other = reader.lines().enumerate().filter_map(move |(line_no, line)| match line {
+            Ok(line) => Some(line),
+            Err(err) => {
+                if skip_err(configs) {
+                    None
+                } else {
+                    RpErr::ReadFromFileErr { file: file_name.clone(), line_no, err: err.to_string() }.termination()
+                }
+            }
+        });
+        Ok(Self::with_other(main, Box::new(other), file, join, policy, configs))
+    }
+
+    fn with_other(
+        main: Pipe, other: Box<dyn Iterator<Item = String>>, file: String, join: Option<ZipJoin>, policy: ZipPolicy,
+        configs: &'static [Config],
+    ) -> Self {
+        let cmd = if join.is_some() { ":zip" } else { ":interleave" };
+        ZipIter { cmd, main, other, file, join, policy, pending: None, done: false, configs }
+    }
+
+    fn next_pair(&mut self) -> Option<(String, String)> {
+        if self.done {
+            return None;
+        }
+        match (self.main.next(), self.other.next()) {
+            (Some(left), Some(right)) => return Some((left, right)),
+            (None, None) => {}
+            (left, right) => match &self.policy {
+                ZipPolicy::Stop => {}
+                ZipPolicy::Pad(fill) => {
+                    return Some((left.unwrap_or_else(|| fill.clone()), right.unwrap_or_else(|| fill.clone())));
+                }
+                ZipPolicy::Error => {
+                    if !skip_err(self.configs) {
+                        RpErr::ZipLengthErr { cmd: self.cmd, file: self.file.clone() }.termination()
+                    }
+                }
+            },
+        }
+        self.done = true;
+        None
+    }
+}
+
+impl Iterator for ZipIter {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pending) = self.pending.take() {
+            return Some(pending);
+        }
+        let (left, right) = self.next_pair()?;
+        match &self.join {
+            Some(join) => Some(join.join(left, right)),
+            None => {
+                self.pending = Some(right);
+                Some(left)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zip(main: &[&str], other: &[&str], join: Option<ZipJoin>, policy: ZipPolicy) -> Vec<String> {
+        let strings = |values: &[&str]| values.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let main = Pipe { iter: Box::new(strings(main).into_iter()), meta: None };
+        let other = Box::new(strings(other).into_iter());
+        ZipIter::with_other(main, other, "f".to_string(), join, policy, &[]).collect()
+    }
+
+    #[test]
+    fn test_zip() {
+        let tab = || Some(ZipJoin::Delimiter(DEFAULT_ZIP_DELIMITER.to_string()));
+        assert_eq!(vec!["1\ta", "2\tb"], zip(&["1", "2", "3"], &["a", "b"], tab(), ZipPolicy::Stop));
+        assert_eq!(vec!["1\ta", "2\tb"], zip(&["1", "2"], &["a", "b", "c"], tab(), ZipPolicy::Stop));
+        assert_eq!(
+            vec!["1\ta", "2\tb", "3\t-"],
+            zip(&["1", "2", "3"], &["a", "b"], tab(), ZipPolicy::Pad("-".to_string()))
+        );
+        assert_eq!(
+            vec!["1=a", "-=b"],
+            zip(&["1"], &["a", "b"], Some(ZipJoin::Fmt("{0}={1}".to_string())), ZipPolicy::Pad("-".to_string()))
+        );
+        assert_eq!(vec!["1\ta"], zip(&["1"], &["a"], tab(), ZipPolicy::Error));
+    }
+
+    #[test]
+    fn test_interleave() {
+        assert_eq!(vec!["1", "a", "2", "b"], zip(&["1", "2", "3"], &["a", "b"], None, ZipPolicy::Stop));
+        assert_eq!(vec!["1", "a", "", "b"], zip(&["1"], &["a", "b"], None, ZipPolicy::Pad(String::new())));
+        assert!(zip(&[], &[], None, ZipPolicy::Error).is_empty());
+    }
+
+    #[test]
+    fn test_zip_open_file_err() {
+        let main = Pipe { iter: Box::new(std::iter::empty()), meta: None };
+        assert!(matches!(
+            ZipIter::new(main, "rp-not-exists-file".to_string(), None, ZipPolicy::Stop, &[]),
+            Err(RpErr::OpenFileErr { .. })
+        ));
+    }
+}
@@ -0,0 +1,242 @@
+use crate::err::RpErr;
+use crate::output::compress::{CompressWriter, Compression};
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+/// 尚未完成的临时文件，`RpErr::termination`直接退出进程时不会执行析构，需要通过此列表清理。
+static TEMP_FILES: TempFiles = TempFiles::new();
+
+/// 临时文件序号，同一进程内同时写入同一个目标文件（例如`:tee`与最终输出）时临时文件互不冲突。
+static TEMP_SEQ: AtomicUsize = AtomicUsize::new(0);
+
+/// 删除所有尚未完成的临时文件。
+pub(crate) fn remove_temp_files() {
+    TEMP_FILES.remove_all();
+}
+
+struct TempFiles {
+    temps: Mutex<Vec<PathBuf>>,
+}
+
+impl TempFiles {
+    const fn new() -> Self {
+        TempFiles { temps: Mutex::new(vec![]) }
+    }
+
+    fn register(&self, temp: PathBuf) {
+        self.temps.lock().unwrap_or_else(PoisonError::into_inner).push(temp);
+    }
+
+    fn unregister(&self, temp: &PathBuf) {
+        self.temps.lock().unwrap_or_else(PoisonError::into_inner).retain(|registered| registered != temp);
+    }
+
+    fn remove_all(&self) {
+        for temp in self.temps.lock().unwrap_or_else(PoisonError::into_inner).drain(..) {
+            let _ = std::fs::remove_file(temp);
+        }
+    }
+}
+
+/// 检查是否可以原子替换目标文件，追加写入时无法原子替换。
+pub(crate) fn check_atomic(cmd: &'static str, append: bool, atomic: bool) -> Result<bool, RpErr> {
+    if append && atomic {
+        Err(RpErr::ArgParseErr {
+            cmd,
+            arg: "atomic",
+            arg_value: "atomic".to_string(),
+            error: "atomic cannot be used with append".to_string(),
+        })
+    } else {
+        Ok(atomic)
+    }
+}
+
+/// 先写入目标文件所在目录的临时文件，全部写入成功后再重命名为目标文件，中途失败时目标文件保持不变。
+pub(in crate::output) struct AtomicFile {
+    file: String,
+    target: PathBuf,
+    temp: PathBuf,
+    writer: Option<CompressWriter<BufWriter<File>>>,
+    temp_files: &'static TempFiles,
+}
+
+impl AtomicFile {
+    /// 目标文件存在且不是普通文件（例如设备文件、命名管道）时无法替换，返回`None`，由调用方直接写入。
+    pub(in crate::output) fn create(file: &str, compression: Option<Compression>) -> Result<Option<Self>, RpErr> {
+        Self::create_in(file, compression, &TEMP_FILES)
+    }
+
+    /// 临时文件登记到指定的列表中。
+    fn create_in(
+        file: &str, compression: Option<Compression>, temp_files: &'static TempFiles,
+    ) -> Result<Option<Self>, RpErr> {
+        let open_err = |err: std::io::Error| RpErr::OpenFileErr { file: file.to_string(), err: err.to_string() };
+        // 目标为符号链接时替换其指向的文件，保留符号链接本身
+        let target = match std::fs::canonicalize(file) {
+            Ok(target) => target,
+            Err(err) if err.kind() == ErrorKind::NotFound => PathBuf::from(file),
+            Err(err) => return Err(open_err(err)),
+        };
+        let permissions = match std::fs::metadata(&target) {
+            Ok(meta) if !meta.is_file() => return Ok(None),
+            Ok(meta) => Some(meta.permissions()),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => return Err(open_err(err)),
+        };
+        let Some(name) = target.file_name() else {
+            return Ok(None);
+        };
+        let seq = TEMP_SEQ.fetch_add(1, Ordering::Relaxed);
+        let temp = target.with_file_name(format!(".{}.{}.{seq}.rp.tmp", name.to_string_lossy(), std::process::id()));
+        let writer = File::create_new(&temp).map_err(open_err)?;
+        temp_files.register(temp.clone());
+        let mut atomic = AtomicFile { file: file.to_string(), target, temp, writer: None, temp_files };
+        if let Some(permissions) = permissions {
+            std::fs::set_permissions(&atomic.temp, permissions).map_err(open_err)?;
+        }
+        atomic.writer = Some(CompressWriter::new(BufWriter::new(writer), compression).map_err(open_err)?);
+        Ok(Some(atomic))
+    }
+
+    pub(in crate::output) fn write_line(&mut self, item: &str, postfix: &str) -> Result<(), RpErr> {
+        let writer = self.writer.as_mut().expect("writer is only taken on commit");
+        writer.write_all(item.as_bytes()).and_then(|_| writer.write_all(postfix.as_bytes())).map_err(|err| {
+            RpErr::WriteToFileErr { file: self.file.clone(), item: item.to_string(), err: err.to_string() }
+        })
+    }
+
+    /// 写入完成，将临时文件重命名为目标文件。指定备份后缀时先将目标文件重命名为目标文件路径加后缀。
+    pub(in crate::output) fn commit(mut self, backup: Option<&str>) -> Result<(), RpErr> {
+        let write_err = |err: std::io::Error| RpErr::WriteToFileErr {
+            file: self.file.clone(),
+            item: String::new(),
+            err: err.to_string(),
+        };
+        let writer = self.writer.take().expect("writer is only taken on commit");
+        let writer = writer.finish().map_err(write_err)?;
+        let file = writer.into_inner().map_err(|err| err.into_error()).map_err(write_err)?;
+        file.sync_all().map_err(write_err)?;
+        drop(file);
+        if let Some(suffix) = backup {
+            let mut backup = self.target.clone().into_os_string();
+            backup.push(suffix);
+            std::fs::rename(&self.target, backup).map_err(write_err)?;
+        }
+        std::fs::rename(&self.temp, &self.target).map_err(write_err)?;
+        self.temp_files.unregister(&self.temp);
+        Ok(())
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        // 未提交时丢弃临时文件
+        if self.writer.take().is_some() {
+            let _ = std::fs::remove_file(&self.temp);
+            self.temp_files.unregister(&self.temp);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rp_atomic_{}_{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn entries(dir: &PathBuf) -> Vec<String> {
+        let mut entries = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn test_commit() {
+        let dir = temp_dir("commit");
+        let file = dir.join("out.txt").to_string_lossy().into_owned();
+        std::fs::write(&file, "old\n").unwrap();
+        let mut atomic = AtomicFile::create(&file, None).unwrap().unwrap();
+        atomic.write_line("new", "\n").unwrap();
+        assert_eq!("old\n", std::fs::read_to_string(&file).unwrap());
+        assert_eq!(2, entries(&dir).len());
+        atomic.commit(None).unwrap();
+        assert_eq!("new\n", std::fs::read_to_string(&file).unwrap());
+        assert_eq!(vec!["out.txt"], entries(&dir));
+    }
+
+    #[test]
+    fn test_same_target() {
+        let dir = temp_dir("same_target");
+        let file = dir.join("out.txt").to_string_lossy().into_owned();
+        let mut first = AtomicFile::create(&file, None).unwrap().unwrap();
+        let mut second = AtomicFile::create(&file, None).unwrap().unwrap();
+        assert_eq!(2, entries(&dir).len());
+        first.write_line("first", "\n").unwrap();
+        second.write_line("second", "\n").unwrap();
+        first.commit(None).unwrap();
+        second.commit(None).unwrap();
+        assert_eq!("second\n", std::fs::read_to_string(&file).unwrap());
+        assert_eq!(vec!["out.txt"], entries(&dir));
+    }
+
+    #[test]
+    fn test_commit_with_backup() {
+        let dir = temp_dir("backup");
+        let file = dir.join("out.txt").to_string_lossy().into_owned();
+        std::fs::write(&file, "old\n").unwrap();
+        let mut atomic = AtomicFile::create(&file, None).unwrap().unwrap();
+        atomic.write_line("new", "\n").unwrap();
+        atomic.commit(Some(".bak")).unwrap();
+        assert_eq!("new\n", std::fs::read_to_string(&file).unwrap());
+        assert_eq!("old\n", std::fs::read_to_string(dir.join("out.txt.bak")).unwrap());
+        assert_eq!(vec!["out.txt", "out.txt.bak"], entries(&dir));
+    }
+
+    #[test]
+    fn test_abort() {
+        let dir = temp_dir("abort");
+        let file = dir.join("out.txt").to_string_lossy().into_owned();
+        std::fs::write(&file, "old\n").unwrap();
+        let mut atomic = AtomicFile::create(&file, None).unwrap().unwrap();
+        atomic.write_line("new", "\n").unwrap();
+        drop(atomic);
+        assert_eq!("old\n", std::fs::read_to_string(&file).unwrap());
+        assert_eq!(vec!["out.txt"], entries(&dir));
+
+        // 使用单独的列表，避免删除并行执行的其他测试的临时文件
+        static TEMP_FILES: TempFiles = TempFiles::new();
+        let mut atomic = AtomicFile::create_in(&file, None, &TEMP_FILES).unwrap().unwrap();
+        atomic.write_line("new", "\n").unwrap();
+        TEMP_FILES.remove_all();
+        assert_eq!(vec!["out.txt"], entries(&dir));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_non_regular_file() {
+        assert!(AtomicFile::create("/dev/null", None).unwrap().is_none());
+
+        let dir = temp_dir("symlink");
+        let target = dir.join("target.txt");
+        std::fs::write(&target, "old\n").unwrap();
+        let link = dir.join("link.txt");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        let mut atomic = AtomicFile::create(&link.to_string_lossy(), None).unwrap().unwrap();
+        atomic.write_line("new", "\n").unwrap();
+        atomic.commit(None).unwrap();
+        assert!(std::fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!("new\n", std::fs::read_to_string(&target).unwrap());
+    }
+}
@@ -0,0 +1,285 @@
+use crate::err::RpErr;
+use flate2::write::GzEncoder;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{BufWriter, Write};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+/// 尚未结束的文件写入器，`RpErr::termination`直接退出进程时不会执行析构，需要通过此列表写入压缩流的结尾。
+static OPEN_FILES: OpenFiles = OpenFiles::new();
+
+/// 为所有尚未结束的文件写入压缩流的结尾，之后再写入这些文件时返回错误。
+pub(crate) fn finish_open_files() {
+    OPEN_FILES.finish_all();
+}
+
+/// 输出文件的压缩格式及压缩级别。
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Compression {
+    /// gzip，级别为0到9，默认为6。
+    Gzip(u32),
+    /// zstd，级别为1到22，默认为3。
+    Zstd(i32),
+}
+
+impl Compression {
+    pub(crate) fn new_gzip(cmd: &'static str, level: Option<&str>) -> Result<Self, RpErr> {
+        match level {
+            Some(level) => parse_level(cmd, level, 0, 9).map(|level| Compression::Gzip(level as u32)),
+            None => Ok(Compression::Gzip(6)),
+        }
+    }
+
+    pub(crate) fn new_zstd(cmd: &'static str, level: Option<&str>) -> Result<Self, RpErr> {
+        match level {
+            Some(level) => parse_level(cmd, level, 1, 22).map(Compression::Zstd),
+            None => Ok(Compression::Zstd(zstd::DEFAULT_COMPRESSION_LEVEL)),
+        }
+    }
+
+    /// 未指定压缩格式时根据扩展名推断，`.gz`为gzip，`.zst`为zstd。
+    pub(crate) fn resolve(compression: Option<Compression>, file: &str) -> Option<Compression> {
+        compression.or_else(|| {
+            let lower = file.to_ascii_lowercase();
+            if lower.ends_with(".gz") {
+                Some(Compression::Gzip(6))
+            } else if lower.ends_with(".zst") {
+                Some(Compression::Zstd(zstd::DEFAULT_COMPRESSION_LEVEL))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+fn parse_level(cmd: &'static str, level: &str, min: i32, max: i32) -> Result<i32, RpErr> {
+    level.parse::<i32>().ok().filter(|level| (min..=max).contains(level)).ok_or_else(|| RpErr::ArgParseErr {
+        cmd,
+        arg: "level",
+        arg_value: level.to_string(),
+        error: format!("compression level must be an integer between {min} and {max}"),
+    })
+}
+
+/// 打开文件并按照压缩格式写入，未指定压缩格式时根据扩展名推断。
+pub(crate) fn open_file(file: &str, append: bool, compression: Option<Compression>) -> io::Result<FileWriter> {
+    let writer = OpenOptions::new().write(true).truncate(!append).append(append).create(true).open(file)?;
+    Ok(OPEN_FILES.register(CompressWriter::new(writer, Compression::resolve(compression, file))?))
+}
+
+type Registered = Mutex<Option<CompressWriter<File>>>;
+
+struct OpenFiles {
+    writers: Mutex<Vec<Weak<Registered>>>,
+}
+
+impl OpenFiles {
+    const fn new() -> Self {
+        OpenFiles { writers: Mutex::new(vec![]) }
+    }
+
+    fn register(&self, writer: CompressWriter<File>) -> FileWriter {
+        let writer = Arc::new(Mutex::new(Some(writer)));
+        let mut writers = self.writers.lock().unwrap_or_else(PoisonError::into_inner);
+        // 顺便清理已经析构的写入器
+        writers.retain(|writer| writer.strong_count() > 0);
+        writers.push(Arc::downgrade(&writer));
+        FileWriter { writer: BufWriter::new(SharedWriter(writer)) }
+    }
+
+    fn finish_all(&self) {
+        for writer in self.writers.lock().unwrap_or_else(PoisonError::into_inner).drain(..) {
+            if let Some(writer) = writer.upgrade()
+                && let Some(writer) = writer.lock().unwrap_or_else(PoisonError::into_inner).take()
+            {
+                let _ = writer.finish();
+            }
+        }
+    }
+}
+
+/// 写入文件，直接退出进程时也会写入压缩流的结尾，参见`finish_open_files`。
+/// 数据先写入缓冲区，仅在缓冲区写满、刷新或结束时才获取锁写入文件，直接退出进程时缓冲区中的数据会丢失。
+pub(crate) struct FileWriter {
+    writer: BufWriter<SharedWriter>,
+}
+
+impl FileWriter {
+    /// 写入缓冲区中的数据和压缩流的结尾。
+    pub(crate) fn finish(self) -> io::Result<()> {
+        let shared = self.writer.into_inner().map_err(|err| err.into_error())?;
+        match shared.0.lock().unwrap_or_else(PoisonError::into_inner).take() {
+            Some(writer) => writer.finish().map(|_| ()),
+            None => Err(finished_err()),
+        }
+    }
+}
+
+impl Write for FileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// 与`OpenFiles`共享的写入器，结束后再写入时返回错误。
+struct SharedWriter(Arc<Registered>);
+
+impl Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.0.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+            Some(writer) => writer.write(buf),
+            None => Err(finished_err()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.0.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+            Some(writer) => writer.flush(),
+            None => Err(finished_err()),
+        }
+    }
+}
+
+fn finished_err() -> io::Error {
+    io::Error::other("file is already finished")
+}
+
+enum Encoder<W: Write> {
+    Plain(W),
+    Gzip(GzEncoder<W>),
+    Zstd(zstd::Encoder<'static, W>),
+}
+
+/// 按照压缩格式写入数据。追加写入时会在文件末尾新增一个gzip成员或zstd帧，解压时与单个压缩流等价。
+/// 结束时需要调用`finish`写入压缩流的结尾，未调用时在析构时写入并忽略错误。
+pub(crate) struct CompressWriter<W: Write> {
+    encoder: Option<Encoder<W>>,
+}
+
+impl<W: Write> CompressWriter<W> {
+    pub(crate) fn new(writer: W, compression: Option<Compression>) -> io::Result<Self> {
+        let encoder = match compression {
+            None => Encoder::Plain(writer),
+            Some(Compression::Gzip(level)) => Encoder::Gzip(GzEncoder::new(writer, flate2::Compression::new(level))),
+            Some(Compression::Zstd(level)) => Encoder::Zstd(zstd::Encoder::new(writer, level)?),
+        };
+        Ok(CompressWriter { encoder: Some(encoder) })
+    }
+
+    /// 写入压缩流的结尾，返回底层的写入器。
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        Self::finish_encoder(self.encoder.take().expect("encoder is only taken on finish"))
+    }
+
+    fn finish_encoder(encoder: Encoder<W>) -> io::Result<W> {
+        match encoder {
+            Encoder::Plain(mut writer) => writer.flush().map(|_| writer),
+            Encoder::Gzip(encoder) => encoder.finish(),
+            Encoder::Zstd(encoder) => encoder.finish(),
+        }
+    }
+
+    fn encoder(&mut self) -> &mut dyn Write {
+        match self.encoder.as_mut().expect("encoder is only taken on finish") {
+            Encoder::Plain(writer) => writer,
+            Encoder::Gzip(encoder) => encoder,
+            Encoder::Zstd(encoder) => encoder,
+        }
+    }
+}
+
+impl<W: Write> Write for CompressWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.encoder().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.encoder().flush()
+    }
+}
+
+impl<W: Write> Drop for CompressWriter<W> {
+    fn drop(&mut self) {
+        if let Some(encoder) = self.encoder.take() {
+            let _ = Self::finish_encoder(encoder);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn compress(compression: Option<Compression>, parts: &[&str]) -> Vec<u8> {
+        let mut bytes = vec![];
+        // 每次写入单独压缩，模拟追加写入
+        for part in parts {
+            let mut writer = CompressWriter::new(vec![], compression).unwrap();
+            writer.write_all(part.as_bytes()).unwrap();
+            bytes.extend(writer.finish().unwrap());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_compression() {
+        assert_eq!(Ok(Compression::Gzip(6)), Compression::new_gzip(":to file", None));
+        assert_eq!(Ok(Compression::Gzip(9)), Compression::new_gzip(":to file", Some("9")));
+        assert!(Compression::new_gzip(":to file", Some("10")).is_err());
+        assert_eq!(Ok(Compression::Zstd(3)), Compression::new_zstd(":to file", None));
+        assert_eq!(Ok(Compression::Zstd(19)), Compression::new_zstd(":to file", Some("19")));
+        assert!(Compression::new_zstd(":to file", Some("0")).is_err());
+        assert!(Compression::new_zstd(":to file", Some("x")).is_err());
+
+        assert_eq!(Some(Compression::Gzip(6)), Compression::resolve(None, "out.log.GZ"));
+        assert_eq!(Some(Compression::Zstd(3)), Compression::resolve(None, "out.zst"));
+        assert_eq!(None, Compression::resolve(None, "out.txt"));
+        assert_eq!(Some(Compression::Gzip(1)), Compression::resolve(Some(Compression::Gzip(1)), "out.zst"));
+    }
+
+    #[test]
+    fn test_compress_writer() {
+        assert_eq!(b"a\nb\n".to_vec(), compress(None, &["a\n", "b\n"]));
+
+        let bytes = compress(Some(Compression::Gzip(6)), &["a\n", "b\n"]);
+        let mut text = String::new();
+        flate2::read::MultiGzDecoder::new(bytes.as_slice()).read_to_string(&mut text).unwrap();
+        assert_eq!("a\nb\n", text);
+
+        let bytes = compress(Some(Compression::Zstd(3)), &["a\n", "b\n"]);
+        assert_eq!(b"a\nb\n".to_vec(), zstd::decode_all(bytes.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_finish_on_drop() {
+        let mut bytes = vec![];
+        {
+            let mut writer = CompressWriter::new(&mut bytes, Some(Compression::Zstd(3))).unwrap();
+            writer.write_all(b"a\n").unwrap();
+        }
+        assert_eq!(b"a\n".to_vec(), zstd::decode_all(bytes.as_slice()).unwrap());
+    }
+
+    #[test]
+    fn test_finish_open_files() {
+        let path = std::env::temp_dir().join(format!("rp_test_finish_open_files_{}.zst", std::process::id()));
+        let file = File::create(&path).unwrap();
+        // 使用单独的列表，避免影响并行执行的其他测试
+        let open_files = OpenFiles::new();
+        let mut writer = open_files.register(CompressWriter::new(file, Some(Compression::Zstd(3))).unwrap());
+        writer.write_all(b"a\n").unwrap();
+        writer.flush().unwrap();
+        open_files.finish_all();
+        // 写入器尚未析构，压缩流已经完整
+        assert_eq!(b"a\n".to_vec(), zstd::decode_all(std::fs::read(&path).unwrap().as_slice()).unwrap());
+        writer.write_all(b"b\n").unwrap();
+        assert!(writer.flush().is_err());
+        assert!(writer.finish().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
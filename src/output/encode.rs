@@ -0,0 +1,130 @@
+use crate::err::RpErr;
+use ::csv::{Terminator, Writer, WriterBuilder};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::io;
+use std::io::Write;
+use std::rc::Rc;
+
+/// 将数据按照分隔符拆分为字段，未指定分隔符时整个数据作为一个字段。
+fn split_fields<'a>(item: &'a str, delimiter: Option<&str>) -> Vec<&'a str> {
+    match delimiter {
+        Some(delimiter) => item.split(delimiter).collect(),
+        None => vec![item],
+    }
+}
+
+/// 将数据编码为JSON：未指定分隔符时编码为字符串，指定分隔符时编码为字段数组，
+/// 使用表头时第一个数据作为键，其余数据编码为对象。
+pub(in crate::output) struct JsonEncoder {
+    delimiter: Option<String>,
+    header: bool,
+    keys: Option<Vec<String>>,
+}
+
+impl JsonEncoder {
+    pub(in crate::output) fn new(delimiter: Option<String>, header: bool) -> Self {
+        JsonEncoder { delimiter, header, keys: None }
+    }
+
+    /// 返回编码后的紧凑JSON，表头数据返回`None`。
+    pub(in crate::output) fn encode(&mut self, item: &str) -> Option<String> {
+        let fields = split_fields(item, self.delimiter.as_deref());
+        if !self.header {
+            return Some(match self.delimiter {
+                Some(_) => Value::from(fields).to_string(),
+                None => Value::from(item).to_string(),
+            });
+        }
+        let Some(keys) = &self.keys else {
+            self.keys = Some(fields.into_iter().map(String::from).collect());
+            return None;
+        };
+        // 手动拼接以保持表头中键的顺序，缺少的字段为`null`，多出的字段以其位置作为键
+        let count = keys.len().max(fields.len());
+        let members = (0..count)
+            .map(|idx| {
+                let key = keys.get(idx).cloned().unwrap_or_else(|| idx.to_string());
+                let value = fields.get(idx).map_or(Value::Null, |field| Value::from(*field));
+                format!("{}:{value}", Value::from(key))
+            })
+            .collect::<Vec<_>>();
+        Some(format!("{{{}}}", members.join(",")))
+    }
+}
+
+/// 按照RFC 4180将数据编码为CSV记录，仅在必要时使用引号包裹字段。
+pub(in crate::output) struct CsvEncoder {
+    delimiter: Option<String>,
+    writer: Writer<SharedBuf>,
+    buf: Rc<RefCell<Vec<u8>>>,
+}
+
+impl CsvEncoder {
+    pub(in crate::output) fn new(delimiter: Option<String>) -> Self {
+        let buf = Rc::new(RefCell::new(vec![]));
+        let writer = WriterBuilder::new().terminator(Terminator::Any(b'\n')).from_writer(SharedBuf(buf.clone()));
+        CsvEncoder { delimiter, writer, buf }
+    }
+
+    /// 返回编码后的记录，不包含结尾的换行符。
+    pub(in crate::output) fn encode(&mut self, item: &str) -> Result<String, RpErr> {
+        let fields = split_fields(item, self.delimiter.as_deref());
+        self.writer
+            .write_record(&fields)
+            .map_err(io::Error::from)
+            .and_then(|_| self.writer.flush())
+            .map_err(|err| RpErr::EncodeCsvErr { item: item.to_string(), err: err.to_string() })?;
+        let mut bytes = std::mem::take(&mut *self.buf.borrow_mut());
+        bytes.pop(); // 丢弃：结尾换行符
+        // 各个字段均为合法UTF-8，编码后仍为合法UTF-8
+        Ok(String::from_utf8(bytes).unwrap_or_default())
+    }
+}
+
+/// CSV写入器的目标，编码后的记录由`CsvEncoder`取出。
+struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json(delimiter: Option<&str>, header: bool, items: &[&str]) -> Vec<String> {
+        let mut encoder = JsonEncoder::new(delimiter.map(String::from), header);
+        items.iter().filter_map(|item| encoder.encode(item)).collect()
+    }
+
+    #[test]
+    fn test_json_encoder() {
+        assert_eq!(vec![r#""a \"b\"""#, r#""c\nd""#], json(None, false, &["a \"b\"", "c\nd"]));
+        assert_eq!(vec![r#"["a","","c"]"#], json(Some(","), false, &["a,,c"]));
+        assert_eq!(
+            vec![r#"{"name":"Jack","age":"18"}"#, r#"{"name":"Tom","age":null}"#, r#"{"name":"A","age":"1","2":"x"}"#],
+            json(Some(","), true, &["name,age", "Jack,18", "Tom", "A,1,x"])
+        );
+        assert_eq!(vec![r#"{"id":"7"}"#], json(None, true, &["id", "7"]));
+        assert!(json(Some(","), true, &["name,age"]).is_empty());
+    }
+
+    #[test]
+    fn test_csv_encoder() {
+        let mut encoder = CsvEncoder::new(Some("\t".to_string()));
+        assert_eq!(Ok("\"a,b\",c".to_string()), encoder.encode("a,b\tc"));
+        assert_eq!(Ok("\"say \"\"hi\"\"\",x".to_string()), encoder.encode("say \"hi\"\tx"));
+        assert_eq!(Ok("\"1\n2\",".to_string()), encoder.encode("1\n2\t"));
+        assert!(matches!(encoder.encode("a\tb\tc"), Err(RpErr::EncodeCsvErr { item, .. }) if item == "a\tb\tc"));
+        let mut encoder = CsvEncoder::new(None);
+        assert_eq!(Ok("\"\"".to_string()), encoder.encode(""));
+        assert_eq!(Ok("plain".to_string()), encoder.encode("plain"));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::err::RpErr;
+use crate::pipe::Pipe;
+use crate::stdout;
+use std::io;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::process::{Command, ExitStatus, Stdio};
+
+/// 启动子进程，将数据逐行写入其标准输入，写入结束后等待子进程退出。
+/// 子进程提前关闭标准输入（例如`head`）时停止写入，不视为错误。
+pub(in crate::output) fn write_to_stdin(program: String, args: Vec<String>, pipe: Pipe) -> Result<(), RpErr> {
+    // 先输出已缓冲的数据，保证与子进程输出的顺序一致
+    stdout::flush()?;
+    let mut child = Command::new(&program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|err| RpErr::SpawnCmdErr { cmd: program.clone(), err: err.to_string() })?;
+    let write_err = |err: io::Error| RpErr::WriteToCmdErr { cmd: program.clone(), err: err.to_string() };
+    if let Some(stdin) = child.stdin.take() {
+        let mut writer = BufWriter::new(stdin);
+        let mut written = Ok(());
+        for item in pipe {
+            written = writer.write_all(item.as_bytes()).and_then(|_| writer.write_all(b"\n"));
+            if written.is_err() {
+                break;
+            }
+        }
+        // 关闭标准输入，子进程才能读取到结尾
+        match written.and_then(|_| writer.flush()) {
+            Err(err) if err.kind() != ErrorKind::BrokenPipe => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(write_err(err));
+            }
+            _ => {}
+        }
+    }
+    // `:peek`等在写入期间输出的数据应在子进程的输出之前
+    stdout::flush()?;
+    check(&program, child.wait())
+}
+
+/// 每`batch`个数据作为参数追加到程序参数之后执行一次，类似`xargs -n`，子进程的标准输入为空。
+pub(in crate::output) fn run_batches(
+    program: String, args: Vec<String>, batch: usize, mut pipe: Pipe,
+) -> Result<(), RpErr> {
+    loop {
+        let items = pipe.by_ref().take(batch).collect::<Vec<_>>();
+        if items.is_empty() {
+            return Ok(());
+        }
+        stdout::flush()?;
+        let status = Command::new(&program)
+            .args(&args)
+            .args(items)
+            .stdin(Stdio::null())
+            .status()
+            .map_err(|err| RpErr::SpawnCmdErr { cmd: program.clone(), err: err.to_string() })?;
+        check(&program, Ok(status))?;
+    }
+}
+
+fn check(program: &str, status: io::Result<ExitStatus>) -> Result<(), RpErr> {
+    match status {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(RpErr::OutputCmdExitErr { cmd: program.to_string(), status: status.to_string() }),
+        Err(err) => Err(RpErr::OutputCmdExitErr { cmd: program.to_string(), status: err.to_string() }),
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+
+    fn pipe(items: &[&str]) -> Pipe {
+        Pipe { iter: Box::new(items.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter()), meta: None }
+    }
+
+    fn temp_file(name: &str) -> String {
+        let file = std::env::temp_dir().join(format!("rp_exec_{}_{name}", std::process::id()));
+        let _ = std::fs::remove_file(&file);
+        file.to_string_lossy().into_owned()
+    }
+
+    fn sh(script: String) -> (String, Vec<String>) {
+        ("sh".to_string(), vec!["-c".to_string(), script, "sh".to_string()])
+    }
+
+    #[test]
+    fn test_write_to_stdin() {
+        let file = temp_file("stdin");
+        let (program, args) = sh(format!("cat > '{file}'"));
+        assert_eq!(Ok(()), write_to_stdin(program, args, pipe(&["a", "b"])));
+        assert_eq!("a\nb\n", std::fs::read_to_string(&file).unwrap());
+
+        // 子进程提前退出时停止写入
+        let items = (0..100000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let items = items.iter().map(String::as_str).collect::<Vec<_>>();
+        let (program, args) = sh("head -n 1 > /dev/null".to_string());
+        assert_eq!(Ok(()), write_to_stdin(program, args, pipe(&items)));
+
+        let (program, args) = sh("cat > /dev/null; exit 3".to_string());
+        assert!(matches!(write_to_stdin(program, args, pipe(&["a"])), Err(RpErr::OutputCmdExitErr { .. })));
+        assert!(matches!(
+            write_to_stdin("rp-not-exists-program".to_string(), vec![], pipe(&["a"])),
+            Err(RpErr::SpawnCmdErr { .. })
+        ));
+    }
+
+    #[test]
+    fn test_run_batches() {
+        let file = temp_file("batches");
+        let (program, args) = sh(format!("echo \"$@\" >> '{file}'"));
+        assert_eq!(Ok(()), run_batches(program, args, 2, pipe(&["a", "b c", "d"])));
+        assert_eq!("a b c\nd\n", std::fs::read_to_string(&file).unwrap());
+
+        let file = temp_file("batches_err");
+        let (program, args) = sh(format!("echo \"$1\" >> '{file}'; [ \"$1\" != b ]"));
+        assert!(matches!(run_batches(program, args, 1, pipe(&["a", "b", "c"])), Err(RpErr::OutputCmdExitErr { .. })));
+        assert_eq!("a\nb\n", std::fs::read_to_string(&file).unwrap());
+    }
+}
@@ -0,0 +1,55 @@
+/// 输出数据时每个数据之后的结束符。
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub(crate) enum LineEnding {
+    /// `\n`。
+    #[default]
+    Lf,
+    /// `\r\n`。
+    CrLf,
+    /// `\0`，用于`xargs -0`等以NUL分隔的程序。
+    Nul,
+    /// 不输出结束符，所有数据直接相连。
+    Empty,
+    /// 自定义的结束符。
+    Custom(String),
+}
+
+impl LineEnding {
+    /// 根据关键字创建结束符，不区分大小写，关键字无效时返回`None`。
+    pub(crate) fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword.to_ascii_lowercase().as_str() {
+            "lf" => Some(LineEnding::Lf),
+            "crlf" => Some(LineEnding::CrLf),
+            "nul" => Some(LineEnding::Nul),
+            "none" => Some(LineEnding::Empty),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Nul => "\0",
+            LineEnding::Empty => "",
+            LineEnding::Custom(ending) => ending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_ending() {
+        assert_eq!(Some(LineEnding::CrLf), LineEnding::from_keyword("CRLF"));
+        assert_eq!(Some(LineEnding::Nul), LineEnding::from_keyword("nul"));
+        assert_eq!(Some(LineEnding::Empty), LineEnding::from_keyword("none"));
+        assert_eq!(None, LineEnding::from_keyword("eol"));
+        assert_eq!("\n", LineEnding::default().as_str());
+        assert_eq!("\0", LineEnding::Nul.as_str());
+        assert_eq!("", LineEnding::Empty.as_str());
+        assert_eq!(";", LineEnding::Custom(";".to_string()).as_str());
+    }
+}
@@ -0,0 +1,364 @@
+pub(crate) mod atomic;
+pub(crate) mod compress;
+mod encode;
+mod exec;
+pub(crate) mod line_ending;
+pub(crate) mod split;
+mod table;
+
+#[cfg(any(windows, target_os = "linux"))]
+use crate::clip;
+use crate::err::RpErr;
+use crate::output::atomic::AtomicFile;
+use crate::output::compress::{Compression, open_file};
+use crate::output::encode::{CsvEncoder, JsonEncoder};
+use crate::output::line_ending::LineEnding;
+use crate::output::split::{SplitBy, SplitWriter};
+use crate::pipe::Pipe;
+use crate::stdout;
+use cmd_help::CmdHelp;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+#[derive(Debug, Clone, Eq, PartialEq, CmdHelp)]
+pub(crate) enum Output {
+    /// :to out     输出到标准输出。
+    ///             :to out[ <ending>]
+    ///                 <ending>    每个数据之后的结束符，可选，参数同`:to file`。
+    ///             未指定元素输出时的默认输出。
+    ///             例如：
+    ///                 :to out nul
+    ///                 :to out eol ", "
+    StdOut { ending: LineEnding },
+    /// :to file    输出到文件。
+    ///             :to file <file>[ append][ <ending>][ gzip|zstd[ <level>]][ atomic]
+    ///                 <file>      文件路径，必选。
+    ///                 append      追加输出而不是覆盖，可选，如果未指定则覆盖源文件。
+    ///                 <ending>    每个数据之后的结束符，可选，如果未指定则默认使用'LF'，支持以下格式：
+    ///                                 lf|crlf             换行符'LF'或'CRLF'。
+    ///                                 nul                 NUL字符，用于`xargs -0`等。
+    ///                                 none                不输出结束符，所有数据直接相连。
+    ///                                 eol <terminator>    自定义结束符，支持`\0`、`\t`等转义。
+    ///                 gzip|zstd   压缩格式，可选，如果未指定则根据扩展名推断，'.gz'为gzip，'.zst'为zstd，否则不压缩。
+    ///                             追加时在文件末尾写入新的压缩流，解压后与连续写入的内容一致。
+    ///                     <level> 压缩级别，可选，gzip为0到9，默认为6，zstd为1到22，默认为3。
+    ///                 atomic      原子替换源文件，可选，不能与`append`同时使用，不支持永不结束的输入。
+    ///                             先写入同目录下的临时文件，全部写入成功后再替换源文件，中途出错时源文件保持不变。
+    ///                             源文件为符号链接时替换其指向的文件；为设备文件、命名管道等非普通文件时直接写入。
+    ///             例如：
+    ///                 :to file out.txt
+    ///                 :to file out.txt append
+    ///                 :to file out.txt crlf
+    ///                 :to file out.txt lf
+    ///                 :to file out.txt append crlf
+    ///                 :to file out.txt append lf
+    ///                 :to file out.txt nul
+    ///                 :to file out.txt eol "\n---\n"
+    ///                 :to file out.log.gz
+    ///                 :to file out.log append zstd 19
+    ///                 :to file out.txt atomic
+    File { file: String, append: bool, atomic: bool, ending: LineEnding, compression: Option<Compression> },
+    /// :to split   拆分输出到多个文件。
+    ///             :to split <pattern> lines <count>|bytes <size>|key <regex>[ append][ <ending>]
+    ///                 <pattern>   文件路径的格式化字符串，必选，支持以下参数：
+    ///                                 {n}     文件序号，从1开始，按照数量或大小拆分时使用。
+    ///                                 {key}   从数据中提取的键，按照键拆分时使用。
+    ///                             文件所在的目录不存在时会自动创建。
+    ///                             更多格式化信息参考`-h fmt`。
+    ///                 lines       按照数量拆分，每个文件最多写入<count>个数据。
+    ///                     <count> 数量，必须为正整数。
+    ///                 bytes       按照大小拆分，每个文件最多写入<size>字节，单个数据超出时独占一个文件。
+    ///                     <size>  大小，必须为正整数，支持K、M、G后缀，按照1024进制计算。
+    ///                 key         按照键拆分，键相同的数据写入同一个文件。
+    ///                     <regex> 提取键的正则，有捕获组时使用第一个捕获组，否则使用整个匹配。
+    ///                             键中的'/'、'\'和NUL字符替换为'_'，不匹配或键为空、'.'、'..'时报错。
+    ///                 append      追加输出而不是覆盖，可选，如果未指定则覆盖已存在的文件。
+    ///                 <ending>    每个数据之后的结束符，可选，参数同`:to file`，按照大小拆分时计入大小。
+    ///             例如：
+    ///                 :to split "part_{n:03}.txt" lines 10000
+    ///                 :to split "part_{n}.txt" bytes 10M crlf
+    ///                 :to split "logs/{key}.log" key '^(\d{4}-\d{2}-\d{2})' append
+    Split { pattern: String, by: SplitBy, append: bool, ending: LineEnding },
+    /// :to inplace 将结果写回输入文件，类似`sed -i`。
+    ///             :to inplace[ backup <suffix>][ <ending>]
+    ///                 backup <suffix> 替换之前将源文件重命名为源文件路径加<suffix>作为备份，可选，如果未指定则不备份。
+    ///                 <ending>        每个数据之后的结束符，可选，参数同`:to file`。
+    ///             仅支持非跟踪模式的`:file`输入，每个文件单独执行一次流水线，结果写回该文件。
+    ///             结果先写入同目录下的临时文件，全部写入成功后再替换源文件，中途出错时源文件保持不变。
+    ///             例如：
+    ///                 :file a.txt b.txt :replace foo bar :to inplace
+    ///                 :file a.txt :uniq :to inplace backup .bak
+    ///                 :file a.txt :trim :to inplace crlf
+    InPlace { backup: Option<String>, ending: LineEnding },
+    /// :to table   将数据拆分为列，按照显示宽度对齐后输出到标准输出，类似`column -t`。
+    ///             中日韩等全角字符按照两个宽度计算。需要缓存全部数据后才能输出。
+    ///             :to table[ <delimiter>][ header][ border]
+    ///                 <delimiter> 列分隔符，可选，如果未指定则按照连续的空白字符拆分。
+    ///                 header      将第一行作为表头，在其后输出分隔线，可选。
+    ///                 border      使用制表符绘制边框，可选。
+    ///             例如：
+    ///                 :to table
+    ///                 :to table , header
+    ///                 :to table "\t" header border
+    Table { delimiter: Option<String>, header: bool, border: bool },
+    /// :to json    将全部数据编码为一个JSON数组输出到标准输出，数组的每个元素占一行。
+    ///             输入结束后才能结束数组，因此不支持永不结束的输入。
+    ///             :to json[ <delimiter>][ header]
+    ///                 <delimiter> 字段分隔符，可选，如果未指定则每个数据编码为字符串，否则拆分后编码为字符串数组。
+    ///                 header      将第一个数据作为键，其余数据编码为对象，可选。
+    ///                             缺少的字段为null，多出的字段以其位置（从0开始）作为键。
+    ///             例如：
+    ///                 :to json
+    ///                 :to json ,
+    ///                 :to json "\t" header
+    Json { delimiter: Option<String>, header: bool },
+    /// :to ndjson  将每个数据编码为一行JSON输出到标准输出，参数同`:to json`。
+    ///             :to ndjson[ <delimiter>][ header]
+    ///             例如：
+    ///                 :to ndjson
+    ///                 :to ndjson , header
+    NdJson { delimiter: Option<String>, header: bool },
+    /// :to csv     将每个数据编码为一条CSV记录输出到标准输出，字段包含逗号、引号或换行符时使用引号包裹并转义。
+    ///             :to csv[ <delimiter>]
+    ///                 <delimiter> 字段分隔符，可选，如果未指定则每个数据作为一个字段。
+    ///             例如：
+    ///                 :to csv
+    ///                 :to csv "\t"
+    Csv { delimiter: Option<String> },
+    /// :to exec    执行外部命令，将数据逐行写入其标准输入，命令以非0状态退出时报错。
+    ///             :to exec[ batch <count>] <program>[ <arg>][...]
+    ///                 batch <count>   每<count>个数据作为参数追加到<arg>之后执行一次命令，类似`xargs -n`，可选。
+    ///                                 <count>必须为正整数，此时命令的标准输入为空。
+    ///                 <program>       需要执行的程序，必选。
+    ///                 <arg>           程序参数，可选，如果以':'开头，需要使用'\:'转义。
+    ///             命令的标准输出和标准错误输出直接打印到终端。
+    ///             例如：
+    ///                 :to exec sort -u
+    ///                 :to exec batch 1 touch
+    ///                 :to exec batch 10 rm -f
+    Exec { program: String, args: Vec<String>, batch: Option<usize> },
+    /// :to clip    输出到剪切板。
+    ///             :to clip[ <ending>]
+    ///                 <ending>    数据之间的分隔符，可选，参数同`:to file`，最后一个数据之后不输出。
+    ///             Linux下通过外部命令写入剪切板，命令的选择同`:clip`。
+    ///             例如：
+    ///                 :to clip
+    ///                 :to clip lf
+    ///                 :to clip crlf
+    ///                 :to clip eol ,
+    #[cfg(any(windows, target_os = "linux"))]
+    Clip { ending: LineEnding },
+    /// :to socket  连接Unix域套接字并输出。
+    ///             :to socket <path>[ <ending>]
+    ///                 <path>      套接字路径，必选，需要有进程在此路径上监听。
+    ///                 <ending>    每个数据之后的结束符，可选，参数同`:to file`。
+    ///             例如：
+    ///                 :to socket /tmp/rp.sock
+    ///                 :to socket /tmp/rp.sock crlf
+    #[cfg(unix)]
+    Socket { path: String, ending: LineEnding },
+}
+
+impl Output {
+    pub(crate) fn new_std_out() -> Self {
+        Output::StdOut { ending: LineEnding::default() }
+    }
+    pub(crate) fn new_std_out_with(ending: LineEnding) -> Self {
+        Output::StdOut { ending }
+    }
+    pub(crate) fn new_file(
+        file: String, append: bool, atomic: bool, ending: LineEnding, compression: Option<Compression>,
+    ) -> Self {
+        Output::File { file, append, atomic, ending, compression }
+    }
+    pub(crate) fn new_split(pattern: String, by: SplitBy, append: bool, ending: LineEnding) -> Self {
+        Output::Split { pattern, by, append, ending }
+    }
+    pub(crate) fn new_in_place(backup: Option<String>, ending: LineEnding) -> Self {
+        Output::InPlace { backup, ending }
+    }
+    pub(crate) fn new_table(delimiter: Option<String>, header: bool, border: bool) -> Self {
+        Output::Table { delimiter, header, border }
+    }
+    pub(crate) fn new_json(delimiter: Option<String>, header: bool) -> Self {
+        Output::Json { delimiter, header }
+    }
+    pub(crate) fn new_nd_json(delimiter: Option<String>, header: bool) -> Self {
+        Output::NdJson { delimiter, header }
+    }
+    pub(crate) fn new_csv(delimiter: Option<String>) -> Self {
+        Output::Csv { delimiter }
+    }
+    pub(crate) fn new_exec(program: String, args: Vec<String>, batch: Option<usize>) -> Self {
+        Output::Exec { program, args, batch }
+    }
+    #[cfg(any(windows, target_os = "linux"))]
+    pub(crate) fn new_clip(ending: LineEnding) -> Self {
+        Output::Clip { ending }
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn new_socket(path: String, ending: LineEnding) -> Self {
+        Output::Socket { path, ending }
+    }
+
+    /// 如果需要缓存全部数据后才能输出，则返回命令名称，此类输出无法用于永不结束的输入。
+    pub(crate) fn buffered_cmd(&self) -> Option<&'static str> {
+        match self {
+            Output::Table { .. } => Some(":to table"),
+            Output::File { atomic: true, .. } => Some(":to file"),
+            Output::Json { .. } => Some(":to json"),
+            #[cfg(any(windows, target_os = "linux"))]
+            Output::Clip { .. } => Some(":to clip"),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_in_place(&self) -> bool {
+        matches!(self, Output::InPlace { .. })
+    }
+
+    /// 将单个输入文件的处理结果写回该文件，仅用于`:to inplace`。
+    pub(crate) fn handle_in_place(&self, file: &str, pipe: Pipe) -> Result<(), RpErr> {
+        let Output::InPlace { backup, ending } = self else {
+            return Err(RpErr::InPlaceInputErr);
+        };
+        // 源文件不是普通文件时无法替换，直接写入会在读取之前清空数据
+        let mut atomic = AtomicFile::create(file, None)?.ok_or_else(|| RpErr::OpenFileErr {
+            file: file.to_string(),
+            err: "in-place editing requires a regular file".to_string(),
+        })?;
+        for item in pipe {
+            atomic.write_line(&item, ending.as_str())?;
+        }
+        atomic.commit(backup.as_deref())
+    }
+
+    pub(crate) fn handle(self, pipe: Pipe) -> Result<(), RpErr> {
+        match self {
+            Output::StdOut { ending } => {
+                for item in pipe {
+                    stdout::write_item(&item, ending.as_str())?;
+                }
+                Ok(())
+            }
+            Output::File { file, atomic: true, ending, compression, .. } => {
+                match AtomicFile::create(&file, Compression::resolve(compression, &file))? {
+                    Some(mut atomic) => {
+                        for item in pipe {
+                            atomic.write_line(&item, ending.as_str())?;
+                        }
+                        atomic.commit(None)
+                    }
+                    // 非普通文件无法替换，直接写入
+                    None => Output::new_file(file, false, false, ending, compression).handle(pipe),
+                }
+            }
+            Output::File { file, append, ending, compression, .. } => {
+                let postfix = ending.as_str();
+                match open_file(&file, append, compression) {
+                    Ok(mut writer) => {
+                        for item in pipe {
+                            write!(writer, "{item}{postfix}").map_err(|err| RpErr::WriteToFileErr {
+                                file: file.clone(),
+                                item: item.to_string(),
+                                err: err.to_string(),
+                            })?
+                        }
+                        writer.finish().map(|_| ()).map_err(|err| RpErr::WriteToFileErr {
+                            file,
+                            item: String::new(),
+                            err: err.to_string(),
+                        })
+                    }
+                    Err(err) => Err(RpErr::OpenFileErr { file, err: err.to_string() }),
+                }
+            }
+            Output::Split { pattern, by, append, ending } => {
+                SplitWriter::new(pattern, by, append, ending).write_all(pipe)
+            }
+            Output::InPlace { .. } => Err(RpErr::InPlaceInputErr),
+            Output::Table { delimiter, header, border } => {
+                let rows = pipe.map(|item| table::split_columns(&item, delimiter.as_deref())).collect();
+                for line in table::render(rows, header, border) {
+                    stdout::write_line(&line)?;
+                }
+                Ok(())
+            }
+            Output::Json { delimiter, header } => {
+                let mut encoder = JsonEncoder::new(delimiter, header);
+                let mut values = pipe.filter_map(|item| encoder.encode(&item)).peekable();
+                if values.peek().is_none() {
+                    return stdout::write_line("[]");
+                }
+                stdout::write_line("[")?;
+                while let Some(value) = values.next() {
+                    let comma = if values.peek().is_some() { "," } else { "" };
+                    stdout::write_line(&format!("  {value}{comma}"))?;
+                }
+                stdout::write_line("]")
+            }
+            Output::NdJson { delimiter, header } => {
+                let mut encoder = JsonEncoder::new(delimiter, header);
+                for item in pipe {
+                    if let Some(value) = encoder.encode(&item) {
+                        stdout::write_line(&value)?;
+                    }
+                }
+                Ok(())
+            }
+            Output::Csv { delimiter } => {
+                let mut encoder = CsvEncoder::new(delimiter);
+                for item in pipe {
+                    stdout::write_line(&encoder.encode(&item)?)?;
+                }
+                Ok(())
+            }
+            Output::Exec { program, args, batch: None } => exec::write_to_stdin(program, args, pipe),
+            Output::Exec { program, args, batch: Some(batch) } => exec::run_batches(program, args, batch, pipe),
+            #[cfg(any(windows, target_os = "linux"))]
+            Output::Clip { ending } => {
+                let text = pipe.collect::<Vec<_>>().join(ending.as_str());
+                clip::set_text(&text)
+            }
+            #[cfg(unix)]
+            Output::Socket { path, ending } => {
+                let socket_err = |err: std::io::Error| RpErr::SocketErr { path: path.clone(), err: err.to_string() };
+                let mut writer = std::io::BufWriter::new(UnixStream::connect(&path).map_err(socket_err)?);
+                let postfix = ending.as_str();
+                for item in pipe {
+                    write!(writer, "{item}{postfix}").map_err(socket_err)?;
+                }
+                writer.flush().map_err(socket_err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::os::unix::net::UnixListener;
+
+    #[test]
+    fn test_to_socket() {
+        let path = std::env::temp_dir().join(format!("rp_output_{}.sock", std::process::id()));
+        let listener = UnixListener::bind(&path).unwrap();
+        let server = std::thread::spawn(move || {
+            let mut content = String::new();
+            listener.accept().unwrap().0.read_to_string(&mut content).unwrap();
+            content
+        });
+        let pipe = Pipe { iter: Box::new(vec!["a".to_string(), "b".to_string()].into_iter()), meta: None };
+        let file = path.to_string_lossy().into_owned();
+        assert_eq!(Ok(()), Output::new_socket(file.clone(), LineEnding::CrLf).handle(pipe));
+        assert_eq!("a\r\nb\r\n", server.join().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let pipe = Pipe { iter: Box::new(std::iter::empty()), meta: None };
+        assert!(matches!(Output::new_socket(file, LineEnding::default()).handle(pipe), Err(RpErr::SocketErr { .. })));
+    }
+}
@@ -0,0 +1,268 @@
+use crate::Integer;
+use crate::err::RpErr;
+use crate::fmt::{FmtArg, fmt_args};
+use crate::output::line_ending::LineEnding;
+use crate::pipe::Pipe;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// 同时保持打开的文件数量上限，超出时关闭最久未写入的文件。
+const MAX_OPEN_FILES: usize = 32;
+
+/// 拆分输出文件的方式。
+#[derive(Debug, Clone)]
+pub(crate) enum SplitBy {
+    /// 每个文件最多写入的数据数量。
+    Lines(usize),
+    /// 每个文件最多写入的字节数，单个数据超出时独占一个文件。
+    Bytes(u64),
+    /// 按照正则从每个数据中提取键，键相同的数据写入同一个文件。有捕获组时使用第一个捕获组，否则使用整个匹配。
+    Key(Regex),
+}
+
+impl PartialEq for SplitBy {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SplitBy::Lines(l), SplitBy::Lines(r)) => l == r,
+            (SplitBy::Bytes(l), SplitBy::Bytes(r)) => l == r,
+            (SplitBy::Key(l), SplitBy::Key(r)) => l.as_str() == r.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SplitBy {}
+
+impl SplitBy {
+    pub(crate) fn new_key(reg: String) -> Result<Self, RpErr> {
+        Regex::new(&reg).map(SplitBy::Key).map_err(|err| RpErr::ParseRegexErr { reg, err: err.to_string() })
+    }
+
+    /// 提取键，键来自数据内容，将路径分隔符和NUL替换为`_`，避免写入目标目录之外，无法作为文件名时报错。
+    fn key(&self, item: &str) -> Result<String, RpErr> {
+        let SplitBy::Key(regex) = self else {
+            return Ok(String::new());
+        };
+        let key = regex
+            .captures(item)
+            .and_then(|caps| caps.get(1).or_else(|| caps.get(0)))
+            .map_or(String::new(), |mat| mat.as_str().replace(['/', '\\', '\0'], "_"));
+        if key.is_empty() || key == "." || key == ".." {
+            Err(RpErr::SplitKeyErr { item: item.to_string(), key })
+        } else {
+            Ok(key)
+        }
+    }
+}
+
+/// 解析文件大小，支持`K`、`M`、`G`后缀（按照1024进制，可以带`B`），必须为正数。
+pub(crate) fn parse_size(value: &str) -> Result<u64, RpErr> {
+    let err = || RpErr::ArgParseErr {
+        cmd: ":to split",
+        arg: "size",
+        arg_value: value.to_string(),
+        error: "size must be a positive integer with optional unit K, M or G".to_string(),
+    };
+    let upper = value.to_ascii_uppercase();
+    let number = upper.strip_suffix('B').unwrap_or(&upper);
+    let (number, unit) = match number.char_indices().last() {
+        Some((idx, 'K')) => (&number[..idx], 1 << 10),
+        Some((idx, 'M')) => (&number[..idx], 1 << 20),
+        Some((idx, 'G')) => (&number[..idx], 1 << 30),
+        _ => (number, 1),
+    };
+    number.parse::<u64>().ok().and_then(|n| n.checked_mul(unit)).filter(|size| *size > 0).ok_or_else(err)
+}
+
+/// 按照拆分方式将数据写入多个文件，文件名由格式化字符串生成：`{n}`为文件序号，从1开始，`{key}`为提取的键。
+pub(in crate::output) struct SplitWriter {
+    pattern: String,
+    by: SplitBy,
+    append: bool,
+    ending: LineEnding,
+    /// 按照最近写入的顺序排列的已打开文件，最后一个为最近写入的文件。
+    files: Vec<(String, BufWriter<File>)>,
+    /// 本次已经创建或清空过的文件，重新打开时需要追加写入。
+    opened: HashSet<String>,
+    /// 当前文件的序号、已写入的数据数量和字节数，仅按照数量和大小拆分时使用。
+    index: usize,
+    lines: usize,
+    bytes: u64,
+}
+
+impl SplitWriter {
+    pub(in crate::output) fn new(pattern: String, by: SplitBy, append: bool, ending: LineEnding) -> Self {
+        SplitWriter { pattern, by, append, ending, files: vec![], opened: HashSet::new(), index: 0, lines: 0, bytes: 0 }
+    }
+
+    pub(in crate::output) fn write_all(mut self, pipe: Pipe) -> Result<(), RpErr> {
+        for item in pipe {
+            self.write(&item)?;
+        }
+        self.files.drain(..).try_for_each(|(file, writer)| close(file, writer))
+    }
+
+    fn write(&mut self, item: &str) -> Result<(), RpErr> {
+        let size = (item.len() + self.ending.as_str().len()) as u64;
+        let rotate = match self.by {
+            SplitBy::Lines(lines) => self.index == 0 || self.lines >= lines,
+            SplitBy::Bytes(bytes) => self.index == 0 || (self.bytes > 0 && self.bytes + size > bytes),
+            SplitBy::Key(_) => false,
+        };
+        if rotate {
+            // 按照数量或大小拆分时不会再写入之前的文件
+            self.files.drain(..).try_for_each(|(file, writer)| close(file, writer))?;
+            self.index += 1;
+            (self.lines, self.bytes) = (0, 0);
+        }
+        self.lines += 1;
+        self.bytes += size;
+        let file = fmt_args(
+            &self.pattern,
+            &[("n", FmtArg::from(self.index.max(1) as Integer)), ("key", FmtArg::from(self.by.key(item)?))],
+        )?;
+        let ending = self.ending.clone();
+        let (file, writer) = self.writer(file)?;
+        write!(writer, "{item}{}", ending.as_str()).map_err(|err| RpErr::WriteToFileErr {
+            file: file.clone(),
+            item: item.to_string(),
+            err: err.to_string(),
+        })
+    }
+
+    /// 获取文件的写入器，并将其移动到最近写入的位置。
+    fn writer(&mut self, file: String) -> Result<&mut (String, BufWriter<File>), RpErr> {
+        if let Some(pos) = self.files.iter().position(|(opened, _)| *opened == file) {
+            let entry = self.files.remove(pos);
+            self.files.push(entry);
+        } else {
+            if self.files.len() >= MAX_OPEN_FILES {
+                let (evicted, writer) = self.files.remove(0);
+                close(evicted, writer)?;
+            }
+            let append = self.append || self.opened.contains(&file);
+            if let Some(dir) = Path::new(&file).parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)
+                    .map_err(|err| RpErr::OpenFileErr { file: file.clone(), err: err.to_string() })?;
+            }
+            let writer = OpenOptions::new()
+                .write(true)
+                .truncate(!append)
+                .append(append)
+                .create(true)
+                .open(&file)
+                .map_err(|err| RpErr::OpenFileErr { file: file.clone(), err: err.to_string() })?;
+            self.opened.insert(file.clone());
+            self.files.push((file, BufWriter::new(writer)));
+        }
+        Ok(self.files.last_mut().unwrap())
+    }
+}
+
+fn close(file: String, mut writer: BufWriter<File>) -> Result<(), RpErr> {
+    writer.flush().map_err(|err| RpErr::WriteToFileErr { file, item: String::new(), err: err.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipe(items: &[&str]) -> Pipe {
+        Pipe { iter: Box::new(items.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter()), meta: None }
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("rp_split_{}_{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn read(dir: &Path, file: &str) -> String {
+        std::fs::read_to_string(dir.join(file)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(Ok(100), parse_size("100"));
+        assert_eq!(Ok(2048), parse_size("2k"));
+        assert_eq!(Ok(3 << 20), parse_size("3MB"));
+        assert_eq!(Ok(1 << 30), parse_size("1G"));
+        assert!(parse_size("0").is_err());
+        assert!(parse_size("K").is_err());
+        assert!(parse_size("1T").is_err());
+        assert!(parse_size("-1").is_err());
+    }
+
+    #[test]
+    fn test_split_by_lines() {
+        let dir = temp_dir("lines");
+        let pattern = format!("{}/part_{{n:02}}.txt", dir.display());
+        let writer = SplitWriter::new(pattern, SplitBy::Lines(2), false, LineEnding::CrLf);
+        assert_eq!(Ok(()), writer.write_all(pipe(&["a", "b", "c", "d", "e"])));
+        assert_eq!("a\r\nb\r\n", read(&dir, "part_01.txt"));
+        assert_eq!("c\r\nd\r\n", read(&dir, "part_02.txt"));
+        assert_eq!("e\r\n", read(&dir, "part_03.txt"));
+    }
+
+    #[test]
+    fn test_split_by_bytes() {
+        let dir = temp_dir("bytes");
+        let pattern = format!("{}/{{n}}", dir.display());
+        let writer = SplitWriter::new(pattern, SplitBy::Bytes(4), false, LineEnding::default());
+        assert_eq!(Ok(()), writer.write_all(pipe(&["a", "b", "long", "c"])));
+        assert_eq!("a\nb\n", read(&dir, "1"));
+        assert_eq!("long\n", read(&dir, "2"));
+        assert_eq!("c\n", read(&dir, "3"));
+    }
+
+    #[test]
+    fn test_split_by_key() {
+        let dir = temp_dir("key");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("2026-01-01.log"), "old\n").unwrap();
+        let pattern = format!("{}/{{key}}.log", dir.display());
+        let by = SplitBy::new_key(r"^(\d{4}-\d{2}-\d{2})".to_string()).unwrap();
+        let mut writer = SplitWriter::new(pattern.clone(), by, false, LineEnding::default());
+        let items = ["2026-01-01 a", "2026-01-02 b", "2026-01-01 c"];
+        for item in items {
+            writer.write(item).unwrap();
+            // 模拟文件句柄被淘汰后重新打开，不能清空已写入的内容
+            writer.files.drain(..).try_for_each(|(file, writer)| close(file, writer)).unwrap();
+        }
+        assert_eq!("2026-01-01 a\n2026-01-01 c\n", read(&dir, "2026-01-01.log"));
+        assert_eq!("2026-01-02 b\n", read(&dir, "2026-01-02.log"));
+
+        let by = SplitBy::new_key(r"^\d{4}".to_string()).unwrap();
+        let writer = SplitWriter::new(pattern, by, true, LineEnding::default());
+        assert_eq!(Ok(()), writer.write_all(pipe(&["2026 d"])));
+        assert_eq!("2026 d\n", read(&dir, "2026.log"));
+        assert!(SplitBy::new_key("(".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_split_key_sanitized() {
+        let dir = temp_dir("key_sanitized");
+        let pattern = format!("{}/logs/{{key}}", dir.display());
+        let by = SplitBy::new_key(r"^\S*".to_string()).unwrap();
+        let writer = SplitWriter::new(pattern.clone(), by.clone(), false, LineEnding::default());
+        assert_eq!(Ok(()), writer.write_all(pipe(&["../../etc/x a", "a/b b", "a\\b c", "a\0b d", "..a e"])));
+        assert_eq!("../../etc/x a\n", read(&dir, "logs/.._.._etc_x"));
+        assert_eq!("a/b b\na\\b c\na\0b d\n", read(&dir, "logs/a_b"));
+        assert_eq!("..a e\n", read(&dir, "logs/..a"));
+        assert_eq!(3, std::fs::read_dir(dir.join("logs")).unwrap().count());
+
+        for item in ["", " a", ".. a", ". a"] {
+            let writer = SplitWriter::new(pattern.clone(), by.clone(), false, LineEnding::default());
+            assert!(matches!(writer.write_all(pipe(&[item])), Err(RpErr::SplitKeyErr { .. })));
+        }
+        let by = SplitBy::new_key(r"\d+".to_string()).unwrap();
+        let writer = SplitWriter::new(pattern, by, false, LineEnding::default());
+        assert_eq!(
+            Err(RpErr::SplitKeyErr { item: "x".to_string(), key: String::new() }),
+            writer.write_all(pipe(&["x"]))
+        );
+    }
+}
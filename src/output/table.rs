@@ -0,0 +1,101 @@
+use unicode_width::UnicodeWidthStr;
+
+/// 将数据按照分隔符拆分为列，未指定分隔符时按照连续的空白字符拆分。
+pub(in crate::output) fn split_columns(item: &str, delimiter: Option<&str>) -> Vec<String> {
+    match delimiter {
+        Some(delimiter) => item.split(delimiter).map(String::from).collect(),
+        None => item.split_whitespace().map(String::from).collect(),
+    }
+}
+
+/// 按照显示宽度对齐各列，中日韩等全角字符按照两个宽度计算。
+/// 无边框时列之间使用两个空格分隔，最后一列不补齐；有表头时在第一行之后输出分隔线。
+pub(in crate::output) fn render(rows: Vec<Vec<String>>, header: bool, border: bool) -> Vec<String> {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; columns];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.width());
+        }
+    }
+    let line = |left: &str, fill: &str, cross: &str, right: &str| {
+        let segments = widths.iter().map(|width| fill.repeat(width + 2)).collect::<Vec<_>>();
+        format!("{left}{}{right}", segments.join(cross))
+    };
+    let mut lines = Vec::with_capacity(rows.len() + 3);
+    if border && columns > 0 {
+        lines.push(line("┌", "─", "┬", "┐"));
+    }
+    let row_count = rows.len();
+    for (idx, row) in rows.into_iter().enumerate() {
+        if border {
+            let cells = widths
+                .iter()
+                .enumerate()
+                .map(|(col, width)| pad(row.get(col).map_or("", String::as_str), *width))
+                .collect::<Vec<_>>();
+            lines.push(format!("│ {} │", cells.join(" │ ")));
+        } else {
+            let last = row.len().saturating_sub(1);
+            let cells = row
+                .iter()
+                .enumerate()
+                .map(|(col, cell)| if col == last { cell.clone() } else { pad(cell, widths[col]) })
+                .collect::<Vec<_>>();
+            lines.push(cells.join("  "));
+        }
+        if header && idx == 0 && row_count > 1 {
+            if border {
+                lines.push(line("├", "─", "┼", "┤"));
+            } else {
+                lines.push(widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>().join("  "));
+            }
+        }
+    }
+    if border && columns > 0 {
+        lines.push(line("└", "─", "┴", "┘"));
+    }
+    lines
+}
+
+fn pad(cell: &str, width: usize) -> String {
+    format!("{cell}{}", " ".repeat(width.saturating_sub(cell.width())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(items: &[&str], delimiter: Option<&str>) -> Vec<Vec<String>> {
+        items.iter().map(|item| split_columns(item, delimiter)).collect()
+    }
+
+    #[test]
+    fn test_split_columns() {
+        assert_eq!(vec!["a", "b", "c"], split_columns("  a \tb  c ", None));
+        assert_eq!(vec!["a", "", "c d"], split_columns("a,,c d", Some(",")));
+        assert!(split_columns("   ", None).is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let table = rows(&["name age city", "张三 18 北京市", "Bob 7"], None);
+        assert_eq!(vec!["name  age  city", "张三  18   北京市", "Bob   7"], render(table.clone(), false, false));
+        assert_eq!(
+            vec!["name  age  city", "----  ---  ------", "张三  18   北京市", "Bob   7"],
+            render(table.clone(), true, false)
+        );
+        assert_eq!(
+            vec![
+                "┌──────┬─────┬────────┐",
+                "│ name │ age │ city   │",
+                "├──────┼─────┼────────┤",
+                "│ 张三 │ 18  │ 北京市 │",
+                "│ Bob  │ 7   │        │",
+                "└──────┴─────┴────────┘",
+            ],
+            render(table, true, true)
+        );
+        assert!(render(vec![], true, true).is_empty());
+    }
+}
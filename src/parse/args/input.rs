@@ -1,28 +1,62 @@
 use crate::err::RpErr;
 use crate::input::Input;
+use crate::input::combine::{ValueList, check_k, split_values};
+use crate::input::csv::{DEFAULT_CSV_DELIMITER, parse_delimiter};
+use crate::input::dates::{check_pattern, parse_datetime};
+use crate::input::random::RandKind;
 use crate::parse::InputResult;
-use crate::parse::args::{parse_arg, parse_arg1, parse_opt_arg, parse_positive_usize};
+use crate::parse::args::{
+    parse_arg, parse_arg1, parse_as, parse_by, parse_opt_arg, parse_positive_usize, parse_tag_nocase, parse_usize,
+};
+use crate::parse::token::input::{parse_date_step, parse_float_pair, parse_int_pair, parse_len_range};
 use std::iter::Peekable;
 
 pub(in crate::parse::args) fn parse_input(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
-    match args.peek() {
-        Some(input) => {
-            let lower_input = input.to_ascii_lowercase();
-            match lower_input.as_str() {
-                ":in" => parse_std_in(args),
-                ":file" => parse_file(args),
-                #[cfg(windows)]
-                ":clip" => parse_clip(args),
-                ":of" => parse_of(args),
-                ":gen" => parse_gen(args),
-                ":repeat" => parse_repeat(args),
-                _ => Ok(Input::new_std_in()),
-            }
-        }
-        None => Ok(Input::new_std_in()),
+    let explicit_cat = parse_tag_nocase(args, ":cat");
+    let mut inputs = Vec::new();
+    while let Some(input) = parse_single_input(args)? {
+        inputs.push(input);
+    }
+    match inputs.len() {
+        0 if explicit_cat => Err(RpErr::MissingArg { cmd: ":cat", arg: "input_cmd" }),
+        0 => Ok(Input::new_std_in()), // 默认从标准输入获取
+        1 if !explicit_cat => Ok(inputs.pop().unwrap()),
+        _ => Ok(Input::new_cat(inputs)),
     }
 }
 
+/// 解析一个输入命令，如果下一个参数不是输入命令则返回`None`。
+fn parse_single_input(args: &mut Peekable<impl Iterator<Item = String>>) -> Result<Option<Input>, RpErr> {
+    let Some(input) = args.peek() else {
+        return Ok(None);
+    };
+    let lower_input = input.to_ascii_lowercase();
+    let input = match lower_input.as_str() {
+        ":in" => parse_std_in(args)?,
+        ":file" => parse_file(args)?,
+        ":csv" => parse_csv(args)?,
+        #[cfg(any(windows, target_os = "linux"))]
+        ":clip" => parse_clip(args)?,
+        #[cfg(unix)]
+        ":socket" => parse_socket(args)?,
+        ":of" => parse_of(args)?,
+        ":text" => parse_text(args)?,
+        ":gen" => parse_gen(args)?,
+        ":repeat" => parse_repeat(args)?,
+        ":exec" => parse_exec(args)?,
+        ":env" => parse_env(args)?,
+        ":ls" => parse_ls(args)?,
+        ":args" => parse_args(args)?,
+        ":rand" => parse_rand(args)?,
+        ":dates" => parse_dates(args)?,
+        ":product" => parse_product(args)?,
+        ":permutations" => parse_permutations(args)?,
+        ":combinations" => parse_combinations(args)?,
+        _ => return Ok(None),
+    };
+    Ok(Some(input))
+}
+
 fn parse_std_in(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
     args.next(); // 消耗命令文本
     Ok(Input::new_std_in())
@@ -30,30 +64,59 @@ fn parse_std_in(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResul
 
 fn parse_file(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
     args.next(); // 消耗命令文本
-    Ok(Input::new_file(parse_arg1(args, ":file", "file")?))
+    let (files, follow) = crate::parse::token::input::split_follow_tag(parse_arg1(args, ":file", "file")?);
+    Ok(Input::new_file(files, follow))
+}
+
+fn parse_csv(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    // :csv <file>|stdin[ <delimiter>][ header]
+    args.next(); // 消耗命令文本
+    let file = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":csv", arg: "file" })?;
+    let file = if file.eq_ignore_ascii_case("stdin") { None } else { Some(file) };
+    let delimiter = if args.peek().is_some_and(|delimiter| !delimiter.eq_ignore_ascii_case("header"))
+        && let Some(delimiter) = parse_opt_arg(args)
+    {
+        parse_delimiter(&delimiter)?
+    } else {
+        DEFAULT_CSV_DELIMITER
+    };
+    Ok(Input::new_csv(file, delimiter, parse_tag_nocase(args, "header")))
 }
 
-#[cfg(windows)]
+#[cfg(any(windows, target_os = "linux"))]
 fn parse_clip(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
     args.next(); // 消耗命令文本
     Ok(Input::new_clip())
 }
 
+#[cfg(unix)]
+fn parse_socket(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let path = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":socket", arg: "path" })?;
+    Ok(Input::new_socket(path, parse_positive_usize(args)))
+}
+
 fn parse_of(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
     args.next(); // 消耗命令文本
     Ok(Input::new_of(parse_arg1(args, ":of", "value")?))
 }
 
+fn parse_text(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    // :text <text>
+    args.next(); // 消耗命令文本
+    Ok(Input::new_text(parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":text", arg: "text" })?))
+}
+
 fn parse_gen(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
     args.next(); // 消耗命令文本
     // ok_or 比 ok_or_else 内存占用更小：MissingArg 字段都是 &'static str（仅引用），无需堆分配
     let range = args.next().ok_or(RpErr::MissingArg { cmd: ":gen", arg: "range" })?;
     match crate::parse::token::input::parse_range_in_gen(&range) {
-        Ok((remaining, (start, end, step))) => {
+        Ok((remaining, range)) => {
             if !remaining.is_empty() {
                 Err(RpErr::UnexpectedRemaining { cmd: ":gen", arg: "range", remaining: remaining.to_string() })
             } else {
-                Ok(Input::new_gen(start, end, step, parse_opt_arg(args)))
+                Ok(Input::new_gen(range, parse_opt_arg(args)))
             }
         }
         Err(e) => {
@@ -70,10 +133,140 @@ fn parse_repeat(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResul
     Ok(Input::new_repeat(value, count))
 }
 
+fn parse_exec(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let stderr = parse_tag_nocase(args, "stderr");
+    let check = parse_tag_nocase(args, "check");
+    let mut cmd_args = parse_arg1(args, ":exec", "program")?.into_iter();
+    let program = cmd_args.next().unwrap(); // parse_arg1保证至少有一个参数
+    Ok(Input::new_exec(program, cmd_args.collect(), stderr, check))
+}
+
+fn parse_env(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    Ok(Input::new_env(parse_opt_arg(args)))
+}
+
+fn parse_ls(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let dir = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":ls", arg: "dir" })?;
+    let recursive = parse_tag_nocase(args, "recursive");
+    Ok(Input::new_ls(dir, recursive))
+}
+
+fn parse_args(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let value = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":args", arg: "value" })?;
+    Input::new_args(value, parse_opt_arg(args))
+}
+
+fn parse_rand(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let count = parse_as::<usize>(args);
+    let seed = if parse_tag_nocase(args, "seed") {
+        Some(parse_by(args, ":rand", "seed", |s| nom::character::complete::u64(s))?)
+    } else {
+        None
+    };
+    let kind = match args.peek().map(|kind| kind.to_ascii_lowercase()).as_deref() {
+        Some("int") => {
+            args.next(); // 消耗`int`
+            let (min, max) = parse_by(args, ":rand", "range", parse_int_pair)?;
+            RandKind::new_int(min, max)?
+        }
+        Some("float") => {
+            args.next(); // 消耗`float`
+            let (min, max) = parse_by(args, ":rand", "range", parse_float_pair)?;
+            RandKind::new_float(min, max)?
+        }
+        Some("str") => {
+            args.next(); // 消耗`str`
+            let (min_len, max_len) = parse_by(args, ":rand", "len", parse_len_range)?;
+            RandKind::new_str(min_len, max_len, parse_opt_arg(args))?
+        }
+        Some("uuid") => {
+            args.next(); // 消耗`uuid`
+            RandKind::Uuid
+        }
+        Some("pick") => {
+            args.next(); // 消耗`pick`
+            RandKind::Pick(parse_arg1(args, ":rand", "value")?)
+        }
+        _ => Err(RpErr::MissingArg { cmd: ":rand", arg: "kind" })?,
+    };
+    Ok(Input::new_rand(kind, count, seed))
+}
+
+fn parse_dates(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let start = parse_arg(args).ok_or(RpErr::MissingArg { cmd: ":dates", arg: "start" })?;
+    let start = parse_datetime("start", &start)?;
+    let end = parse_arg(args).ok_or(RpErr::MissingArg { cmd: ":dates", arg: "end" })?;
+    let end = parse_datetime("end", &end)?;
+    let step = parse_by(args, ":dates", "step", parse_date_step)?;
+    let pattern = if args.peek().is_some_and(|pattern| !pattern.eq_ignore_ascii_case("fmt")) {
+        parse_opt_arg(args)
+    } else {
+        None
+    };
+    if let Some(pattern) = &pattern {
+        check_pattern(pattern)?;
+    }
+    Ok(Input::new_dates(start, end, step, pattern, parse_combine_fmt(args, ":dates")?))
+}
+
+fn parse_product(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let mut lists = Vec::new();
+    while let Some(list) = args.peek()
+        && !list.eq_ignore_ascii_case("fmt")
+        && let Some(list) = parse_opt_arg(args)
+    {
+        lists.push(ValueList::parse(&list));
+    }
+    if lists.is_empty() {
+        return Err(RpErr::MissingArg { cmd: ":product", arg: "list" });
+    }
+    Ok(Input::new_product(lists, parse_combine_fmt(args, ":product")?))
+}
+
+fn parse_permutations(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let (k, values) = parse_k_values(args, ":permutations")?;
+    Ok(Input::new_permutations(k, values, parse_combine_fmt(args, ":permutations")?))
+}
+
+fn parse_combinations(args: &mut Peekable<impl Iterator<Item = String>>) -> InputResult {
+    args.next(); // 消耗命令文本
+    let (k, values) = parse_k_values(args, ":combinations")?;
+    Ok(Input::new_combinations(k, values, parse_combine_fmt(args, ":combinations")?))
+}
+
+fn parse_k_values(
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str,
+) -> Result<(usize, Vec<String>), RpErr> {
+    let k = check_k(cmd, parse_usize(cmd, "k", args)?)?;
+    let values = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd, arg: "values" })?;
+    Ok((k, split_values(&values)))
+}
+
+/// 解析可选的`fmt <fmt>`。
+fn parse_combine_fmt(
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str,
+) -> Result<Option<String>, RpErr> {
+    if parse_tag_nocase(args, "fmt") {
+        Ok(Some(parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd, arg: "fmt" })?))
+    } else {
+        Ok(None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::Integer;
+    use crate::input::dates::DateStep;
+    use crate::input::range::GenRange;
     use crate::parse::args::build_args;
 
     #[test]
@@ -91,15 +284,43 @@ mod tests {
         assert_eq!(None, args.next());
     }
 
+    #[test]
+    fn test_parse_cat() {
+        let mut args = build_args(":of header :file body.txt :of footer :uniq");
+        assert_eq!(
+            Ok(Input::new_cat(vec![
+                Input::new_of(vec!["header".to_string()]),
+                Input::new_file(vec!["body.txt".to_string()], false),
+                Input::new_of(vec!["footer".to_string()]),
+            ])),
+            parse_input(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":cat :in :gen 1,3");
+        assert_eq!(
+            Ok(Input::new_cat(vec![Input::new_std_in(), Input::new_gen(GenRange::Integer(1, 3, 1), None)])),
+            parse_input(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":cat :in");
+        assert_eq!(Ok(Input::new_cat(vec![Input::new_std_in()])), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":cat :uniq");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":cat", arg: "input_cmd" }), parse_input(&mut args));
+    }
+
     #[test]
     fn test_parse_file() {
         let mut args = build_args(":file name");
-        assert_eq!(Ok(Input::new_file(vec!["name".to_string()])), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_file(vec!["name".to_string()], false)), parse_input(&mut args));
         assert!(args.next().is_none());
 
         let mut args = build_args(":file name1 name2 \\] :123");
         assert_eq!(
-            Ok(Input::new_file(vec!["name1".to_string(), "name2".to_string(), "\\]".to_string()])),
+            Ok(Input::new_file(vec!["name1".to_string(), "name2".to_string(), "\\]".to_string()], false)),
             parse_input(&mut args)
         );
         assert_eq!(Some(":123".to_string()), args.next());
@@ -109,18 +330,43 @@ mod tests {
         assert!(args.next().is_none());
 
         let mut args = build_args(":file [ ]");
-        assert_eq!(Ok(Input::new_file(vec!["[".to_string(), "]".to_string()])), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_file(vec!["[".to_string(), "]".to_string()], false)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":file name follow :123");
+        assert_eq!(Ok(Input::new_file(vec!["name".to_string()], true)), parse_input(&mut args));
+        assert_eq!(Some(":123".to_string()), args.next());
+
+        let mut args = build_args(":file name1 name2 FOLLOW");
+        assert_eq!(Ok(Input::new_file(vec!["name1".to_string(), "name2".to_string()], true)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":file follow");
+        assert_eq!(Ok(Input::new_file(vec!["follow".to_string()], false)), parse_input(&mut args));
         assert!(args.next().is_none());
     }
 
     #[test]
-    #[cfg(windows)]
+    #[cfg(any(windows, target_os = "linux"))]
     fn test_parse_clip() {
         let mut args = build_args(":clip");
         assert_eq!(Ok(Input::new_clip()), parse_input(&mut args));
         assert!(args.next().is_none());
     }
 
+    #[test]
+    fn test_parse_text() {
+        let mut args = build_args(":text a\\nb\\tc :upper");
+        assert_eq!(Ok(Input::new_text("a\nb\tc".to_string())), parse_input(&mut args));
+        assert_eq!(Some(":upper".to_string()), args.next());
+
+        let mut args = Vec::from([":text".to_string(), "a\nb\n".to_string()]).into_iter().peekable();
+        assert_eq!(Ok(Input::new_text("a\nb\n".to_string())), parse_input(&mut args));
+
+        let mut args = build_args(":text");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":text", arg: "text" }), parse_input(&mut args));
+    }
+
     #[test]
     fn test_parse_of() {
         let mut args = build_args(":of text");
@@ -146,23 +392,23 @@ mod tests {
     #[test]
     fn test_parse_gen() {
         let mut args = build_args(":gen 0");
-        assert_eq!(Ok(Input::new_gen(0, Integer::MAX, 1, None)), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_gen(GenRange::Integer(0, Integer::MAX, 1), None)), parse_input(&mut args));
         assert!(args.next().is_none());
 
         let mut args = build_args(":gen 0,");
-        assert_eq!(Ok(Input::new_gen(0, Integer::MAX, 1, None)), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_gen(GenRange::Integer(0, Integer::MAX, 1), None)), parse_input(&mut args));
         assert!(args.next().is_none());
 
         let mut args = build_args(":gen 0,10");
-        assert_eq!(Ok(Input::new_gen(0, 10, 1, None)), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_gen(GenRange::Integer(0, 10, 1), None)), parse_input(&mut args));
         assert!(args.next().is_none());
 
         let mut args = build_args(":gen 0,10,2");
-        assert_eq!(Ok(Input::new_gen(0, 10, 2, None)), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_gen(GenRange::Integer(0, 10, 2), None)), parse_input(&mut args));
         assert!(args.next().is_none());
 
         let mut args = build_args(":gen 0,,2");
-        assert_eq!(Ok(Input::new_gen(0, Integer::MAX, 2, None)), parse_input(&mut args));
+        assert_eq!(Ok(Input::new_gen(GenRange::Integer(0, Integer::MAX, 2), None)), parse_input(&mut args));
         assert!(args.next().is_none());
 
         let mut args = build_args(":gen");
@@ -183,6 +429,20 @@ mod tests {
                 if ":gen".eq(cmd) && "range".eq(arg) && "abc".eq(&arg_value)
         ));
         assert!(args.next().is_none());
+
+        let mut args = build_args(":gen 0,1,0.1 {v:.2}");
+        assert_eq!(
+            Ok(Input::new_gen(GenRange::Float(0.0, 1.0, 0.1), Some("{v:.2}".to_string()))),
+            parse_input(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":gen a,z,2");
+        assert_eq!(Ok(Input::new_gen(GenRange::Char('a', 'z', 2), None)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":gen 0.5,,-1");
+        assert!(matches!(parse_input(&mut args), Err(RpErr::ArgParseErr { cmd: ":gen", arg: "range", .. })));
     }
 
     #[test]
@@ -199,4 +459,275 @@ mod tests {
         assert_eq!(Err(RpErr::MissingArg { cmd: ":repeat", arg: "value" }), parse_input(&mut args));
         assert!(args.next().is_none());
     }
+
+    #[test]
+    fn test_parse_exec() {
+        let mut args = build_args(":exec ls");
+        assert_eq!(Ok(Input::new_exec("ls".to_string(), vec![], false, false)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":exec ls -l \\:a :uniq");
+        assert_eq!(
+            Ok(Input::new_exec("ls".to_string(), vec!["-l".to_string(), ":a".to_string()], false, false)),
+            parse_input(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":exec stderr check cargo build");
+        assert_eq!(
+            Ok(Input::new_exec("cargo".to_string(), vec!["build".to_string()], true, true)),
+            parse_input(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":exec check ls");
+        assert_eq!(Ok(Input::new_exec("ls".to_string(), vec![], false, true)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":exec stderr");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":exec", arg: "program" }), parse_input(&mut args));
+        assert!(args.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_env() {
+        let mut args = build_args(":env");
+        assert_eq!(Ok(Input::new_env(None)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":env CARGO_ :uniq");
+        assert_eq!(Ok(Input::new_env(Some("CARGO_".to_string()))), parse_input(&mut args));
+        assert_eq!(Some(":uniq".to_string()), args.next());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_parse_socket() {
+        let mut args = build_args(":socket /tmp/rp.sock");
+        assert_eq!(Ok(Input::new_socket("/tmp/rp.sock".to_string(), None)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":socket /tmp/rp.sock 2 :uniq");
+        assert_eq!(Ok(Input::new_socket("/tmp/rp.sock".to_string(), Some(2))), parse_input(&mut args));
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":socket");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":socket", arg: "path" }), parse_input(&mut args));
+    }
+
+    #[test]
+    fn test_parse_ls() {
+        let mut args = build_args(":ls src");
+        assert_eq!(Ok(Input::new_ls("src".to_string(), false)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":ls src recursive :uniq");
+        assert_eq!(Ok(Input::new_ls("src".to_string(), true)), parse_input(&mut args));
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":ls :uniq");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":ls", arg: "dir" }), parse_input(&mut args));
+        assert_eq!(Some(":uniq".to_string()), args.next());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let mut args = build_args(":args a:b");
+        assert_eq!(Ok(Input::new_args("a:b".to_string(), None).unwrap()), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":args a,b , :uniq");
+        assert_eq!(Ok(Input::new_args("a,b".to_string(), Some(",".to_string())).unwrap()), parse_input(&mut args));
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":args abc ''");
+        assert!(matches!(parse_input(&mut args), Err(RpErr::ArgParseErr { cmd: ":args", arg: "sep", .. })));
+
+        let mut args = build_args(":args");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":args", arg: "value" }), parse_input(&mut args));
+        assert!(args.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_rand() {
+        let mut args = build_args(":rand int 1,100");
+        assert_eq!(Ok(Input::new_rand(RandKind::new_int(1, 100).unwrap(), None, None)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":rand 10 seed 42 float -0.5,1");
+        assert_eq!(
+            Ok(Input::new_rand(RandKind::new_float(-0.5, 1.0).unwrap(), Some(10), Some(42))),
+            parse_input(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":rand 5 str 4,8 a-f0-9 :uniq");
+        assert_eq!(
+            Ok(Input::new_rand(RandKind::new_str(4, Some(8), Some("a-f0-9".to_string())).unwrap(), Some(5), None)),
+            parse_input(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":rand 0 UUID");
+        assert_eq!(Ok(Input::new_rand(RandKind::Uuid, Some(0), None)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":rand pick a b :uniq");
+        assert_eq!(
+            Ok(Input::new_rand(RandKind::Pick(vec!["a".to_string(), "b".to_string()]), None, None)),
+            parse_input(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":rand");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":rand", arg: "kind" }), parse_input(&mut args));
+
+        let mut args = build_args(":rand int");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":rand", arg: "range" }), parse_input(&mut args));
+
+        let mut args = build_args(":rand int 1,2x");
+        assert_eq!(
+            Err(RpErr::UnexpectedRemaining { cmd: ":rand", arg: "range", remaining: "x".to_string() }),
+            parse_input(&mut args)
+        );
+
+        let mut args = build_args(":rand seed abc uuid");
+        assert!(matches!(parse_input(&mut args), Err(RpErr::ArgParseErr { cmd: ":rand", arg: "seed", .. })));
+
+        let mut args = build_args(":rand int 3,1");
+        assert!(matches!(parse_input(&mut args), Err(RpErr::ArgParseErr { cmd: ":rand", arg: "range", .. })));
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let mut args = build_args(":csv a.csv");
+        assert_eq!(Ok(Input::new_csv(Some("a.csv".to_string()), b',', false)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":csv STDIN ; header :uniq");
+        assert_eq!(Ok(Input::new_csv(None, b';', true)), parse_input(&mut args));
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":csv a.csv header");
+        assert_eq!(Ok(Input::new_csv(Some("a.csv".to_string()), b',', true)), parse_input(&mut args));
+
+        let mut args = build_args(":csv a.csv ;; header");
+        assert!(matches!(parse_input(&mut args), Err(RpErr::ArgParseErr { cmd: ":csv", arg: "delimiter", .. })));
+
+        let mut args = build_args(":csv :uniq");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":csv", arg: "file" }), parse_input(&mut args));
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        let datetime = |s: &str| parse_datetime("start", s).unwrap();
+        let mut args = build_args(":dates 2024-01-01 2024-01-31 1d");
+        assert_eq!(
+            Ok(Input::new_dates(datetime("2024-01-01"), datetime("2024-01-31"), DateStep::Seconds(86400), None, None)),
+            parse_input(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":dates 2024-01-01T08:00 2024-12-01 1mo %Y%m fmt logs/{v}.log :uniq");
+        assert_eq!(
+            Ok(Input::new_dates(
+                datetime("2024-01-01 08:00"),
+                datetime("2024-12-01"),
+                DateStep::Months(1),
+                Some("%Y%m".to_string()),
+                Some("logs/{v}.log".to_string())
+            )),
+            parse_input(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":dates 2024-01-01 2024-01-02 1d FMT {ts}");
+        assert_eq!(
+            Ok(Input::new_dates(
+                datetime("2024-01-01"),
+                datetime("2024-01-02"),
+                DateStep::Seconds(86400),
+                None,
+                Some("{ts}".to_string())
+            )),
+            parse_input(&mut args)
+        );
+
+        let mut args = build_args(":dates 2024-01-01 2024-01-02 1d %Y fmt");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":dates", arg: "fmt" }), parse_input(&mut args));
+
+        let mut args = build_args(":dates 2024-01-01");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":dates", arg: "end" }), parse_input(&mut args));
+
+        let mut args = build_args(":dates 2024-01-01 2024-01-02");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":dates", arg: "step" }), parse_input(&mut args));
+
+        let mut args = build_args(":dates 2024-01-01 2024-01-02 0d");
+        assert!(matches!(parse_input(&mut args), Err(RpErr::ArgParseErr { cmd: ":dates", arg: "step", .. })));
+
+        let mut args = build_args(":dates 2024-02-30 2024-03-02 1d");
+        assert!(matches!(parse_input(&mut args), Err(RpErr::ArgParseErr { cmd: ":dates", arg: "start", .. })));
+
+        let mut args = build_args(":dates 2024-01-01 2024-01-02 1d %Q");
+        assert!(matches!(parse_input(&mut args), Err(RpErr::ArgParseErr { cmd: ":dates", arg: "pattern", .. })));
+    }
+
+    #[test]
+    fn test_parse_product() {
+        let mut args = build_args(":product host=x,y 80,443 fmt {host}:{1} :limit 1");
+        assert_eq!(
+            Ok(Input::new_product(
+                vec![
+                    ValueList::new(Some("host".to_string()), vec!["x".to_string(), "y".to_string()]),
+                    ValueList::new(None, vec!["80".to_string(), "443".to_string()])
+                ],
+                Some("{host}:{1}".to_string())
+            )),
+            parse_input(&mut args)
+        );
+        assert_eq!(Some(":limit".to_string()), args.next());
+
+        let mut args = build_args(":product a");
+        assert_eq!(
+            Ok(Input::new_product(vec![ValueList::new(None, vec!["a".to_string()])], None)),
+            parse_input(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":product fmt {0}");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":product", arg: "list" }), parse_input(&mut args));
+
+        let mut args = build_args(":product a,b fmt");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":product", arg: "fmt" }), parse_input(&mut args));
+    }
+
+    #[test]
+    fn test_parse_permutations_and_combinations() {
+        let values = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut args = build_args(":permutations 2 a,b,c");
+        assert_eq!(Ok(Input::new_permutations(2, values.clone(), None)), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":combinations 2 a,b,c fmt {0}-{1}");
+        assert_eq!(Ok(Input::new_combinations(2, values, Some("{0}-{1}".to_string()))), parse_input(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":permutations 0 a,b,c");
+        assert!(matches!(
+            parse_input(&mut args),
+            Err(RpErr::ArgParseErr { cmd: ":permutations", arg: "k", arg_value, .. }) if arg_value == "0"
+        ));
+
+        let mut args = build_args(":permutations x a,b,c");
+        assert!(matches!(
+            parse_input(&mut args),
+            Err(RpErr::InvalidNonNegativeIntArg { cmd: ":permutations", arg: "k", arg_value }) if arg_value == "x"
+        ));
+
+        let mut args = build_args(":combinations");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":combinations", arg: "k" }), parse_input(&mut args));
+
+        let mut args = build_args(":combinations 2");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":combinations", arg: "values" }), parse_input(&mut args));
+    }
 }
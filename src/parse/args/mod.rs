@@ -2,9 +2,13 @@ use crate::err::RpErr;
 use crate::input::Input;
 use crate::op::Op;
 use crate::output::Output;
+use crate::output::compress::Compression;
+use crate::output::line_ending::LineEnding;
+use crate::parse::RpParseErr;
 use crate::parse::args::input::parse_input;
 use crate::parse::args::op::parse_ops;
 use crate::parse::args::output::parse_output;
+use nom::IResult;
 use std::iter::Peekable;
 use std::str::FromStr;
 
@@ -99,46 +103,69 @@ fn parse_as<T: FromStr>(args: &mut Peekable<impl Iterator<Item = String>>) -> Op
     }
 }
 
-/// 解析一般的文件信息`file[ append][ <crlf|lf>]`
+/// 使用Token模式的解析器解析下一个参数，参数必须被完全解析
+fn parse_by<T>(
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str, arg: &'static str,
+    parser: impl for<'a> Fn(&'a str) -> IResult<&'a str, T, RpParseErr<'a>>,
+) -> Result<T, RpErr> {
+    let value = args.next().ok_or(RpErr::MissingArg { cmd, arg })?;
+    match parser(&value) {
+        Ok((remaining, res)) => {
+            if remaining.is_empty() {
+                Ok(res)
+            } else {
+                Err(RpErr::UnexpectedRemaining { cmd, arg, remaining: remaining.to_string() })
+            }
+        }
+        Err(err) => {
+            let error = err.to_string();
+            Err(RpErr::ArgParseErr { cmd, arg, arg_value: value, error })
+        }
+    }
+}
+
+/// 解析一般的文件信息`file[ append][ <ending>]`
 /// 如果`optional`为`false`，则file参数必须非命令格式
 fn parse_general_file_info(
-    args: &mut Peekable<impl Iterator<Item = String>>, optional: bool,
-) -> Option<(String, bool, Option<bool>)> {
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str, optional: bool,
+) -> Result<Option<(String, bool, LineEnding)>, RpErr> {
     if let Some(file) = if optional { parse_opt_arg(args) } else { parse_arg(args) } {
-        let (append, crlf) = if let Some(append_or_postfix) = args.peek() {
-            if append_or_postfix.eq_ignore_ascii_case("append") {
-                args.next(); // 消耗`append`
-                if let Some(crlf) = args.peek() {
-                    if crlf.eq_ignore_ascii_case("crlf") {
-                        args.next(); // 消耗`crlf`
-                        (true, Some(true))
-                    } else if crlf.eq_ignore_ascii_case("lf") {
-                        args.next(); // 消耗`lf`
-                        (true, Some(false))
-                    } else {
-                        (true, None)
-                    }
-                } else {
-                    (true, None)
-                }
-            } else if append_or_postfix.eq_ignore_ascii_case("crlf") {
-                args.next(); // 消耗`crlf`
-                (false, Some(true))
-            } else if append_or_postfix.eq_ignore_ascii_case("lf") {
-                args.next(); // 消耗`lf`
-                (false, Some(false))
-            } else {
-                (false, None)
-            }
-        } else {
-            (false, None)
-        };
-        Some((file, append, crlf))
+        let append = parse_tag_nocase(args, "append");
+        Ok(Some((file, append, parse_line_ending(args, cmd)?)))
     } else {
-        None
+        Ok(None)
+    }
+}
+
+/// 解析结束符`lf|crlf|nul|none|eol <terminator>`，未指定时为`LF`
+fn parse_line_ending(
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str,
+) -> Result<LineEnding, RpErr> {
+    if parse_tag_nocase(args, "eol") {
+        Ok(LineEnding::Custom(parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd, arg: "terminator" })?))
+    } else if let Some(ending) = args.peek().and_then(|keyword| LineEnding::from_keyword(keyword)) {
+        args.next();
+        Ok(ending)
+    } else {
+        Ok(LineEnding::default())
     }
 }
 
+/// 解析压缩格式`gzip|zstd[ <level>]`，下一个参数为纯数字时作为压缩级别
+fn parse_compression(
+    args: &mut Peekable<impl Iterator<Item = String>>, cmd: &'static str,
+) -> Result<Option<Compression>, RpErr> {
+    let new_compression = if parse_tag_nocase(args, "gzip") {
+        Compression::new_gzip
+    } else if parse_tag_nocase(args, "zstd") {
+        Compression::new_zstd
+    } else {
+        return Ok(None);
+    };
+    let level = args.next_if(|level| !level.is_empty() && level.bytes().all(|b| b.is_ascii_digit()));
+    new_compression(cmd, level.as_deref()).map(Some)
+}
+
 fn parse_usize(
     cmd: &'static str, arg: &'static str, args: &mut Peekable<impl Iterator<Item = String>>,
 ) -> Result<usize, RpErr> {
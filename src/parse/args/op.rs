@@ -1,10 +1,15 @@
 use crate::err::RpErr;
+use crate::op::json::{JsonPath, JsonPolicy};
 use crate::op::trim::{TrimArg, TrimPos};
+use crate::op::zip::{DEFAULT_ZIP_DELIMITER, ZipJoin, ZipPolicy};
 use crate::op::{CaseArg, JoinInfo, Op, PeekArg, RegArg, SortBy, TakeDropMode};
 use crate::parse::args::condition::parse_cond;
+use crate::parse::args::output::parse_output;
 use crate::parse::args::{
-    parse_arg, parse_as, parse_general_file_info, parse_opt_arg, parse_positive_usize, parse_tag_nocase, parse_usize,
+    parse_arg, parse_as, parse_compression, parse_general_file_info, parse_opt_arg, parse_positive_usize,
+    parse_tag_nocase, parse_usize,
 };
+use crate::parse::token::op::is_zip_keyword;
 use crate::parse::token::parse_usize_range;
 use crate::parse::{OpOptResult, OpResult, OpsResult};
 use crate::{Float, Integer};
@@ -24,6 +29,7 @@ fn parse_op(args: &mut Peekable<impl Iterator<Item = String>>) -> OpOptResult {
             let lower_op = op.to_ascii_lowercase();
             Ok(match lower_op.as_str() {
                 ":peek" => Some(parse_peek(args)?),
+                ":tee" => Some(parse_tee(args)?),
                 ":lower" => Some(parse_case(CaseArg::Lower, args)?),
                 ":upper" => Some(parse_case(CaseArg::Upper, args)?),
                 ":case" => Some(parse_case(CaseArg::Switch, args)?),
@@ -38,6 +44,8 @@ fn parse_op(args: &mut Peekable<impl Iterator<Item = String>>) -> OpOptResult {
                 ":ltrimr" => Some(parse_trim_regex(":ltrimr", TrimPos::Head, args)?),
                 ":rtrimr" => Some(parse_trim_regex(":rtrimr", TrimPos::Tail, args)?),
                 ":reg" => Some(parse_reg(args)?),
+                ":fmt" => Some(parse_fmt(args)?),
+                ":json" => Some(parse_json(args)?),
                 ":limit" => Some(parse_limit(args)?),
                 ":skip" => Some(parse_skip(args)?),
                 ":slice" => Some(parse_slice(args)?),
@@ -48,6 +56,8 @@ fn parse_op(args: &mut Peekable<impl Iterator<Item = String>>) -> OpOptResult {
                 ":take" => Some(parse_take_or_take_while(args)?),
                 ":count" => Some(parse_count(args)?),
                 ":sort" => Some(parse_sort(args)?),
+                ":zip" => Some(parse_zip(args)?),
+                ":interleave" => Some(parse_interleave(args)?),
                 _ => None,
             })
         }
@@ -55,13 +65,31 @@ fn parse_op(args: &mut Peekable<impl Iterator<Item = String>>) -> OpOptResult {
     }
 }
 
+fn parse_tee(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    // :tee <output>
+    args.next();
+    if !args.peek().is_some_and(|to| to.eq_ignore_ascii_case(":to")) {
+        return Err(RpErr::MissingArg { cmd: ":tee", arg: "output" });
+    }
+    Op::new_tee(parse_output(args)?)
+}
+
 fn parse_peek(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    // :peek[ <file>[ append][ <ending>][ gzip|zstd[ <level>]]][ fmt <fmt>]
     args.next();
-    if let Some((file, append, crlf)) = parse_general_file_info(args, true) {
-        Ok(Op::Peek(PeekArg::File { file, append, crlf }))
+    let arg = if !args.peek().is_some_and(|arg| arg.eq_ignore_ascii_case("fmt"))
+        && let Some((file, append, ending)) = parse_general_file_info(args, ":peek", true)?
+    {
+        PeekArg::File { file, append, ending, compression: parse_compression(args, ":peek")? }
     } else {
-        Ok(Op::Peek(PeekArg::StdOut))
-    }
+        PeekArg::StdOut
+    };
+    let fmt = if parse_tag_nocase(args, "fmt") {
+        Some(parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":peek", arg: "fmt" })?)
+    } else {
+        None
+    };
+    Ok(Op::new_peek(arg, fmt))
 }
 
 fn parse_sum(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
@@ -122,6 +150,29 @@ fn parse_reg(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
     }
 }
 
+fn parse_fmt(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    // :fmt <fmt>
+    args.next();
+    let fmt = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":fmt", arg: "fmt" })?;
+    Ok(Op::Fmt { fmt })
+}
+
+fn parse_json(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    // :json <path>[ drop|keep|error]
+    args.next();
+    let path = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":json", arg: "path" })?;
+    let path = JsonPath::parse(&path)?;
+    let policy = if parse_tag_nocase(args, "drop") {
+        JsonPolicy::Drop
+    } else if parse_tag_nocase(args, "keep") {
+        JsonPolicy::Keep
+    } else {
+        parse_tag_nocase(args, "error");
+        JsonPolicy::Error
+    };
+    Ok(Op::new_json(path, policy))
+}
+
 fn parse_limit(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
     args.next();
     let count = parse_usize(":limit", "count", args)?;
@@ -244,12 +295,65 @@ fn parse_sort(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
     Ok(Op::new_sort(sort_by, desc))
 }
 
+fn parse_zip(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    let file = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":zip", arg: "file" })?;
+    let join = if parse_tag_nocase(args, "fmt") {
+        ZipJoin::Fmt(parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":zip", arg: "fmt" })?)
+    } else if args.peek().is_some_and(|delimiter| !is_zip_keyword(delimiter))
+        && let Some(delimiter) = parse_opt_arg(args)
+    {
+        ZipJoin::Delimiter(delimiter)
+    } else {
+        ZipJoin::Delimiter(DEFAULT_ZIP_DELIMITER.to_string())
+    };
+    Ok(Op::new_zip(file, join, parse_zip_policy(args)))
+}
+
+fn parse_interleave(args: &mut Peekable<impl Iterator<Item = String>>) -> OpResult {
+    args.next();
+    let file = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":interleave", arg: "file" })?;
+    Ok(Op::new_interleave(file, parse_zip_policy(args)))
+}
+
+fn parse_zip_policy(args: &mut Peekable<impl Iterator<Item = String>>) -> ZipPolicy {
+    if parse_tag_nocase(args, "pad") {
+        ZipPolicy::Pad(parse_opt_arg(args).unwrap_or_default())
+    } else if parse_tag_nocase(args, "error") {
+        ZipPolicy::Error
+    } else {
+        parse_tag_nocase(args, "stop");
+        ZipPolicy::Stop
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::op::CaseArg;
+    use crate::output::Output;
+    use crate::output::compress::Compression;
+    use crate::output::line_ending::LineEnding;
     use crate::parse::args::build_args;
 
+    #[test]
+    fn test_parse_tee() {
+        let mut args = build_args(":tee :to file all.txt append :uniq");
+        assert_eq!(
+            Ok(Some(
+                Op::new_tee(Output::new_file("all.txt".to_string(), true, false, LineEnding::default(), None)).unwrap()
+            )),
+            parse_op(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+        let mut args = build_args(":tee :to out :to clip");
+        assert_eq!(Ok(Some(Op::new_tee(Output::new_std_out()).unwrap())), parse_op(&mut args));
+        let mut args = build_args(":tee :to inplace :uniq");
+        assert!(matches!(parse_op(&mut args), Err(RpErr::ArgParseErr { cmd: ":tee", arg: "output", .. })));
+        let mut args = build_args(":tee :uniq");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":tee", arg: "output" }), parse_op(&mut args));
+    }
+
     #[test]
     fn test_non_match() {
         let mut args = build_args("");
@@ -438,6 +542,75 @@ mod tests {
         assert!(parse_op(&mut build_args(":rtrimr ")).is_err());
     }
 
+    #[test]
+    fn test_parse_fmt() {
+        let mut args = build_args(":fmt {file}:{line}:{v} :uniq");
+        assert_eq!(Ok(Some(Op::Fmt { fmt: "{file}:{line}:{v}".to_string() })), parse_op(&mut args));
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":fmt :uniq");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":fmt", arg: "fmt" }), parse_op(&mut args));
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let path = |path| JsonPath::parse(path).unwrap();
+        let mut args = build_args(":json .a[0] drop :uniq");
+        assert_eq!(Ok(Some(Op::new_json(path(".a[0]"), JsonPolicy::Drop))), parse_op(&mut args));
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":json . keep");
+        assert_eq!(Ok(Some(Op::new_json(path("."), JsonPolicy::Keep))), parse_op(&mut args));
+
+        let mut args = build_args(":json .a");
+        assert_eq!(Ok(Some(Op::new_json(path(".a"), JsonPolicy::Error))), parse_op(&mut args));
+
+        let mut args = build_args(":json a");
+        assert!(matches!(parse_op(&mut args), Err(RpErr::ArgParseErr { cmd: ":json", arg: "path", .. })));
+
+        let mut args = build_args(":json");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":json", arg: "path" }), parse_op(&mut args));
+    }
+
+    #[test]
+    fn test_parse_peek_fmt() {
+        let mut args = build_args(":peek fmt {line}:{v}");
+        assert_eq!(Ok(Some(Op::new_peek(PeekArg::StdOut, Some("{line}:{v}".to_string())))), parse_op(&mut args));
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":peek out.txt append FMT {v}");
+        assert_eq!(
+            Ok(Some(Op::new_peek(
+                PeekArg::File {
+                    file: "out.txt".to_string(),
+                    append: true,
+                    ending: LineEnding::default(),
+                    compression: None
+                },
+                Some("{v}".to_string())
+            ))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":peek out.log zstd fmt {v}");
+        assert_eq!(
+            Ok(Some(Op::new_peek(
+                PeekArg::File {
+                    file: "out.log".to_string(),
+                    append: false,
+                    ending: LineEnding::default(),
+                    compression: Some(Compression::Zstd(3))
+                },
+                Some("{v}".to_string())
+            ))),
+            parse_op(&mut args)
+        );
+
+        let mut args = build_args(":peek fmt");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":peek", arg: "fmt" }), parse_op(&mut args));
+    }
+
     #[test]
     fn test_parse_reg() {
         // 基本匹配 - 无 count
@@ -675,4 +848,55 @@ mod tests {
         assert_eq!(Ok(Some(Op::Sum { fmt: Some("Total: {v}".to_string()) })), parse_op(&mut args));
         assert!(args.next().is_none());
     }
+
+    #[test]
+    fn test_parse_zip() {
+        let mut args = build_args(":zip f.txt");
+        assert_eq!(
+            Ok(Some(Op::new_zip(
+                "f.txt".to_string(),
+                ZipJoin::Delimiter(DEFAULT_ZIP_DELIMITER.to_string()),
+                ZipPolicy::Stop
+            ))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":zip f.txt , pad NULL :uniq");
+        assert_eq!(
+            Ok(Some(Op::new_zip(
+                "f.txt".to_string(),
+                ZipJoin::Delimiter(",".to_string()),
+                ZipPolicy::Pad("NULL".to_string())
+            ))),
+            parse_op(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+
+        let mut args = build_args(":zip f.txt fmt {0}={1} error");
+        assert_eq!(
+            Ok(Some(Op::new_zip("f.txt".to_string(), ZipJoin::Fmt("{0}={1}".to_string()), ZipPolicy::Error))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":zip");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":zip", arg: "file" }), parse_op(&mut args));
+
+        let mut args = build_args(":zip f.txt fmt");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":zip", arg: "fmt" }), parse_op(&mut args));
+    }
+
+    #[test]
+    fn test_parse_interleave() {
+        let mut args = build_args(":interleave f.txt pad");
+        assert_eq!(
+            Ok(Some(Op::new_interleave("f.txt".to_string(), ZipPolicy::Pad(String::new())))),
+            parse_op(&mut args)
+        );
+        assert!(args.next().is_none());
+
+        let mut args = build_args(":interleave");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":interleave", arg: "file" }), parse_op(&mut args));
+    }
 }
@@ -1,7 +1,12 @@
 use crate::err::RpErr;
 use crate::output::Output;
+use crate::output::atomic::check_atomic;
+use crate::output::split::{SplitBy, parse_size};
 use crate::parse::{OutputResult, args};
-use args::parse_general_file_info;
+use args::{
+    parse_arg1, parse_compression, parse_general_file_info, parse_line_ending, parse_opt_arg, parse_positive_usize,
+    parse_tag_nocase,
+};
 use std::iter::Peekable;
 
 pub(in crate::parse::args) fn parse_output(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
@@ -14,8 +19,17 @@ pub(in crate::parse::args) fn parse_output(args: &mut Peekable<impl Iterator<Ite
                 let lower_output = output.to_ascii_lowercase();
                 match lower_output.as_str() {
                     "file" => parse_file(args),
-                    #[cfg(windows)]
+                    "split" => parse_split(args),
+                    "inplace" => parse_in_place(args),
+                    "table" => parse_table(args),
+                    "json" => parse_json(args, Output::new_json),
+                    "ndjson" => parse_json(args, Output::new_nd_json),
+                    "csv" => parse_csv(args),
+                    "exec" => parse_exec(args),
+                    #[cfg(any(windows, target_os = "linux"))]
                     "clip" => parse_clip(args),
+                    #[cfg(unix)]
+                    "socket" => parse_socket(args),
                     "out" => parse_std_out(args),
                     _ => Ok(Output::new_std_out()),
                 }
@@ -29,33 +43,246 @@ pub(in crate::parse::args) fn parse_output(args: &mut Peekable<impl Iterator<Ite
 
 fn parse_file(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
     args.next(); // 消耗`file`
-    if let Some((file, append, crlf)) = parse_general_file_info(args, false) {
-        Ok(Output::new_file(file, append, crlf))
+    if let Some((file, append, ending)) = parse_general_file_info(args, ":to file", false)? {
+        let compression = parse_compression(args, ":to file")?;
+        let atomic = check_atomic(":to file", append, parse_tag_nocase(args, "atomic"))?;
+        Ok(Output::new_file(file, append, atomic, ending, compression))
     } else {
         Err(RpErr::MissingArg { cmd: ":to file", arg: "file" })
     }
 }
 
-#[cfg(windows)]
-fn parse_clip(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
-    args.next(); // 消耗`clip`
-    let postfix = if let Some(crlf) = args.peek() {
-        if crlf.eq_ignore_ascii_case("crlf") {
-            args.next(); // 消耗`crlf`
-            Some(true)
-        } else if crlf.eq_ignore_ascii_case("lf") {
-            args.next(); // 消耗`lf`
-            Some(false)
-        } else {
-            None
-        }
+fn parse_split(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    // :to split <pattern> lines <count>|bytes <size>|key <regex>[ append][ <ending>]
+    args.next(); // 消耗`split`
+    let pattern = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":to split", arg: "pattern" })?;
+    let by = if parse_tag_nocase(args, "lines") {
+        SplitBy::Lines(parse_positive_usize(args).ok_or(RpErr::MissingArg { cmd: ":to split", arg: "count" })?)
+    } else if parse_tag_nocase(args, "bytes") {
+        SplitBy::Bytes(parse_size(&parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":to split", arg: "size" })?)?)
+    } else if parse_tag_nocase(args, "key") {
+        SplitBy::new_key(parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":to split", arg: "regex" })?)?
+    } else {
+        return Err(RpErr::MissingArg { cmd: ":to split", arg: "lines|bytes|key" });
+    };
+    let append = parse_tag_nocase(args, "append");
+    Ok(Output::new_split(pattern, by, append, parse_line_ending(args, ":to split")?))
+}
+
+fn parse_in_place(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    // :to inplace[ backup <suffix>][ <ending>]
+    args.next(); // 消耗`inplace`
+    let backup = if parse_tag_nocase(args, "backup") {
+        Some(parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":to inplace", arg: "suffix" })?)
+    } else {
+        None
+    };
+    Ok(Output::new_in_place(backup, parse_line_ending(args, ":to inplace")?))
+}
+
+fn parse_table(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    // :to table[ <delimiter>][ header][ border]
+    args.next(); // 消耗`table`
+    let delimiter = if args
+        .peek()
+        .is_some_and(|delimiter| !delimiter.eq_ignore_ascii_case("header") && !delimiter.eq_ignore_ascii_case("border"))
+    {
+        parse_opt_arg(args)
+    } else {
+        None
+    };
+    let header = parse_tag_nocase(args, "header");
+    Ok(Output::new_table(delimiter, header, parse_tag_nocase(args, "border")))
+}
+
+fn parse_json(
+    args: &mut Peekable<impl Iterator<Item = String>>, new_output: fn(Option<String>, bool) -> Output,
+) -> OutputResult {
+    // :to json|ndjson[ <delimiter>][ header]
+    args.next(); // 消耗`json`或`ndjson`
+    let delimiter = if args.peek().is_some_and(|delimiter| !delimiter.eq_ignore_ascii_case("header")) {
+        parse_opt_arg(args)
+    } else {
+        None
+    };
+    Ok(new_output(delimiter, parse_tag_nocase(args, "header")))
+}
+
+fn parse_csv(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    // :to csv[ <delimiter>]
+    args.next(); // 消耗`csv`
+    Ok(Output::new_csv(parse_opt_arg(args)))
+}
+
+fn parse_exec(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    // :to exec[ batch <count>] <program>[ <arg>][...]
+    args.next(); // 消耗`exec`
+    let batch = if parse_tag_nocase(args, "batch") {
+        Some(parse_positive_usize(args).ok_or(RpErr::MissingArg { cmd: ":to exec", arg: "count" })?)
     } else {
         None
     };
-    Ok(Output::new_clip(postfix))
+    let mut cmd_args = parse_arg1(args, ":to exec", "program")?.into_iter();
+    let program = cmd_args.next().unwrap(); // parse_arg1保证至少有一个参数
+    Ok(Output::new_exec(program, cmd_args.collect(), batch))
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+fn parse_clip(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    args.next(); // 消耗`clip`
+    Ok(Output::new_clip(parse_line_ending(args, ":to clip")?))
+}
+
+#[cfg(unix)]
+fn parse_socket(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
+    args.next(); // 消耗`socket`
+    let path = parse_opt_arg(args).ok_or(RpErr::MissingArg { cmd: ":to socket", arg: "path" })?;
+    Ok(Output::new_socket(path, parse_line_ending(args, ":to socket")?))
 }
 
 fn parse_std_out(args: &mut Peekable<impl Iterator<Item = String>>) -> OutputResult {
     args.next(); // 消耗`out`
-    Ok(Output::new_std_out())
+    Ok(Output::new_std_out_with(parse_line_ending(args, ":to out")?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::compress::Compression;
+    use crate::output::line_ending::LineEnding;
+    use crate::parse::args::build_args;
+
+    #[test]
+    fn test_parse_file() {
+        let mut args = build_args(":to file out.log gzip");
+        assert_eq!(
+            Ok(Output::new_file(
+                "out.log".to_string(),
+                false,
+                false,
+                LineEnding::default(),
+                Some(Compression::Gzip(6))
+            )),
+            parse_output(&mut args)
+        );
+        let mut args = build_args(":to file out.log append crlf ZSTD 19 :uniq");
+        assert_eq!(
+            Ok(Output::new_file("out.log".to_string(), true, false, LineEnding::CrLf, Some(Compression::Zstd(19)))),
+            parse_output(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+        let mut args = build_args(":to file out.log nul");
+        assert_eq!(
+            Ok(Output::new_file("out.log".to_string(), false, false, LineEnding::Nul, None)),
+            parse_output(&mut args)
+        );
+        let mut args = build_args(":to file out.log append eol \\0\\n");
+        assert_eq!(
+            Ok(Output::new_file("out.log".to_string(), true, false, LineEnding::Custom("\0\n".to_string()), None)),
+            parse_output(&mut args)
+        );
+        let mut args = build_args(":to file out.log gzip atomic :uniq");
+        assert_eq!(
+            Ok(Output::new_file("out.log".to_string(), false, true, LineEnding::default(), Some(Compression::Gzip(6)))),
+            parse_output(&mut args)
+        );
+        assert_eq!(Some(":uniq".to_string()), args.next());
+        let mut args = build_args(":to file out.log append atomic");
+        assert!(matches!(parse_output(&mut args), Err(RpErr::ArgParseErr { cmd: ":to file", arg: "atomic", .. })));
+        let mut args = build_args(":to file out.log eol");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to file", arg: "terminator" }), parse_output(&mut args));
+        let mut args = build_args(":to file out.log gzip 10");
+        assert!(matches!(parse_output(&mut args), Err(RpErr::ArgParseErr { cmd: ":to file", arg: "level", .. })));
+    }
+
+    #[test]
+    fn test_parse_std_out() {
+        let mut args = build_args(":to out");
+        assert_eq!(Ok(Output::new_std_out()), parse_output(&mut args));
+        let mut args = build_args(":to out none");
+        assert_eq!(Ok(Output::new_std_out_with(LineEnding::Empty)), parse_output(&mut args));
+        let mut args = build_args(":to out eol ,");
+        assert_eq!(Ok(Output::new_std_out_with(LineEnding::Custom(",".to_string()))), parse_output(&mut args));
+    }
+
+    #[test]
+    fn test_parse_split() {
+        let mut args = build_args(":to split part_{n}.txt lines 100 append");
+        assert_eq!(
+            Ok(Output::new_split("part_{n}.txt".to_string(), SplitBy::Lines(100), true, LineEnding::default())),
+            parse_output(&mut args)
+        );
+        let mut args = build_args(":to split {n} bytes 2M crlf");
+        assert_eq!(
+            Ok(Output::new_split("{n}".to_string(), SplitBy::Bytes(2 << 20), false, LineEnding::CrLf)),
+            parse_output(&mut args)
+        );
+        let mut args = build_args(":to split {key}.log key ^(\\S+)");
+        assert_eq!(
+            Ok(Output::new_split(
+                "{key}.log".to_string(),
+                SplitBy::new_key("^(\\S+)".to_string()).unwrap(),
+                false,
+                LineEnding::default()
+            )),
+            parse_output(&mut args)
+        );
+
+        let mut args = build_args(":to split {n} lines 0");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to split", arg: "count" }), parse_output(&mut args));
+        let mut args = build_args(":to split {n} bytes 0");
+        assert!(matches!(parse_output(&mut args), Err(RpErr::ArgParseErr { cmd: ":to split", arg: "size", .. })));
+        let mut args = build_args(":to split {n}");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to split", arg: "lines|bytes|key" }), parse_output(&mut args));
+    }
+
+    #[test]
+    fn test_parse_table() {
+        let mut args = build_args(":to table");
+        assert_eq!(Ok(Output::new_table(None, false, false)), parse_output(&mut args));
+        let mut args = build_args(":to table , header border");
+        assert_eq!(Ok(Output::new_table(Some(",".to_string()), true, true)), parse_output(&mut args));
+        let mut args = build_args(":to table border");
+        assert_eq!(Ok(Output::new_table(None, false, true)), parse_output(&mut args));
+        let mut args = build_args(":to table \\t :uniq");
+        assert_eq!(Ok(Output::new_table(Some("\t".to_string()), false, false)), parse_output(&mut args));
+    }
+
+    #[test]
+    fn test_parse_encode() {
+        let mut args = build_args(":to json");
+        assert_eq!(Ok(Output::new_json(None, false)), parse_output(&mut args));
+        let mut args = build_args(":to json , header");
+        assert_eq!(Ok(Output::new_json(Some(",".to_string()), true)), parse_output(&mut args));
+        let mut args = build_args(":to ndjson header");
+        assert_eq!(Ok(Output::new_nd_json(None, true)), parse_output(&mut args));
+        let mut args = build_args(":to csv \\t");
+        assert_eq!(Ok(Output::new_csv(Some("\t".to_string()))), parse_output(&mut args));
+        let mut args = build_args(":to csv");
+        assert_eq!(Ok(Output::new_csv(None)), parse_output(&mut args));
+    }
+
+    #[test]
+    fn test_parse_exec() {
+        let mut args = build_args(":to exec sort -u :uniq");
+        assert_eq!(Ok(Output::new_exec("sort".to_string(), vec!["-u".to_string()], None)), parse_output(&mut args));
+        let mut args = build_args(":to exec batch 2 echo \\:x");
+        assert_eq!(Ok(Output::new_exec("echo".to_string(), vec![":x".to_string()], Some(2))), parse_output(&mut args));
+        let mut args = build_args(":to exec batch 0 echo");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to exec", arg: "count" }), parse_output(&mut args));
+        let mut args = build_args(":to exec");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to exec", arg: "program" }), parse_output(&mut args));
+    }
+
+    #[test]
+    fn test_parse_in_place() {
+        let mut args = build_args(":to inplace");
+        assert_eq!(Ok(Output::new_in_place(None, LineEnding::default())), parse_output(&mut args));
+        let mut args = build_args(":to inplace backup .bak crlf");
+        assert_eq!(Ok(Output::new_in_place(Some(".bak".to_string()), LineEnding::CrLf)), parse_output(&mut args));
+        let mut args = build_args(":to inplace lf");
+        assert_eq!(Ok(Output::new_in_place(None, LineEnding::Lf)), parse_output(&mut args));
+        let mut args = build_args(":to inplace backup");
+        assert_eq!(Err(RpErr::MissingArg { cmd: ":to inplace", arg: "suffix" }), parse_output(&mut args));
+    }
 }
@@ -1,15 +1,22 @@
-use crate::Integer;
+use crate::err::RpErr;
 use crate::input::Input;
+use crate::input::combine::{ValueList, check_k, split_values};
+use crate::input::csv::{DEFAULT_CSV_DELIMITER, parse_delimiter};
+use crate::input::dates::{DateStep, check_pattern, parse_datetime};
+use crate::input::random::RandKind;
+use crate::input::range::GenRange;
 use crate::parse::RpParseErr;
-use crate::parse::token::parse_integer;
-use crate::parse::token::{arg_exclude_cmd, cmd_arg1};
+use crate::parse::token::{arg_end, arg_exclude_cmd, arg1, cmd_arg1, heredoc, map_res_failure};
+use crate::parse::token::{parse_float, parse_integer};
+use crate::{Float, Integer};
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
-use nom::character::complete::space1;
-use nom::character::complete::{char, usize};
-use nom::combinator::{map, opt, success, verify};
+use nom::character::complete::{anychar, char, one_of, satisfy, u32, u64, usize};
+use nom::character::complete::{multispace1, space1};
+use nom::combinator::{map, map_opt, not, opt, success, value, verify};
 use nom::error::context;
-use nom::sequence::{preceded, terminated};
+use nom::multi::many1;
+use nom::sequence::{delimited, preceded, separated_pair, terminated};
 use nom::{IResult, Parser};
 
 pub(in crate::parse) type InputIResult<'a> = IResult<&'a str, Input, RpParseErr<'a>>;
@@ -18,19 +25,42 @@ pub(in crate::parse) fn parse_input(input: &str) -> InputIResult<'_> {
     context(
         "Input",
         alt((
-            parse_std_in,
-            parse_file,
-            #[cfg(windows)]
-            parse_clip,
-            parse_of,
-            parse_gen,
-            parse_repeat,
+            map(preceded((tag_no_case(":cat"), space1), many1(parse_single_input)), Input::new_cat),
+            map(many1(parse_single_input), |mut inputs| {
+                if inputs.len() == 1 { inputs.pop().unwrap() } else { Input::new_cat(inputs) }
+            }),
             context("Input::StdIn", map(success(()), |_| Input::new_std_in())), // 默认从标准输入获取
         )),
     )
     .parse(input)
 }
 
+fn parse_single_input(input: &str) -> InputIResult<'_> {
+    alt((
+        parse_std_in,
+        parse_file,
+        parse_csv,
+        #[cfg(any(windows, target_os = "linux"))]
+        parse_clip,
+        #[cfg(unix)]
+        parse_socket,
+        parse_of,
+        parse_text,
+        parse_gen,
+        parse_repeat,
+        parse_exec,
+        parse_env,
+        parse_ls,
+        parse_args,
+        parse_rand,
+        parse_dates,
+        parse_product,
+        parse_permutations,
+        parse_combinations,
+    ))
+    .parse(input)
+}
+
 fn parse_std_in(input: &str) -> InputIResult<'_> {
     context("Input::StdIn", map((tag_no_case(":in"), context("(trailing_space1)", space1)), |_| Input::new_std_in()))
         .parse(input)
@@ -40,13 +70,24 @@ fn parse_file(input: &str) -> InputIResult<'_> {
     context(
         "Input::File",
         map(terminated(cmd_arg1(":file", "<file>"), context("(trailing_space1)", space1)), |files| {
-            Input::new_file(files)
+            let (files, follow) = split_follow_tag(files);
+            Input::new_file(files, follow)
         }),
     )
     .parse(input)
 }
 
-#[cfg(windows)]
+/// 从`:file`的参数中分离结尾的`follow`标记，仅有一个参数时视为文件名。
+pub(in crate::parse) fn split_follow_tag(mut files: Vec<String>) -> (Vec<String>, bool) {
+    if files.len() > 1 && files.last().is_some_and(|f| f.eq_ignore_ascii_case("follow")) {
+        files.pop();
+        (files, true)
+    } else {
+        (files, false)
+    }
+}
+
+#[cfg(any(windows, target_os = "linux"))]
 fn parse_clip(input: &str) -> InputIResult<'_> {
     context("Input::Clip", map((tag_no_case(":clip"), context("(trailing_space1)", space1)), |_| Input::new_clip()))
         .parse(input)
@@ -62,6 +103,29 @@ fn parse_of(input: &str) -> InputIResult<'_> {
     .parse(input)
 }
 
+fn parse_text(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Text",
+        alt((
+            map(
+                preceded(
+                    (tag_no_case(":text"), space1),
+                    terminated(heredoc, context("(trailing_space1)", multispace1)), // Here Document可能以换行结尾
+                ),
+                Input::new_text,
+            ),
+            map(
+                terminated(
+                    preceded((tag_no_case(":text"), space1), context("<text>", arg_exclude_cmd)),
+                    context("(trailing_space1)", space1),
+                ),
+                Input::new_text,
+            ),
+        )),
+    )
+    .parse(input)
+}
+
 fn parse_gen(input: &str) -> InputIResult<'_> {
     context(
         "Input::Gen",
@@ -76,32 +140,114 @@ fn parse_gen(input: &str) -> InputIResult<'_> {
                 ),
                 context("(trailing_space1)", space1),
             ),
-            |((start, end, step), fmt)| Input::new_gen(start, end, step, fmt),
+            |(range, fmt)| Input::new_gen(range, fmt),
         ),
     )
     .parse(input)
 }
 
-pub(in crate::parse) fn parse_range_in_gen(input: &str) -> IResult<&str, (Integer, Integer, Integer), RpParseErr<'_>> {
+/// 依次尝试解析整数、浮点数和字符范围，整数范围后紧跟浮点数字符或未能解析的步长时尝试浮点数范围。
+pub(in crate::parse) fn parse_range_in_gen(input: &str) -> IResult<&str, GenRange, RpParseErr<'_>> {
+    alt((
+        map(terminated(range_parts(parse_integer, parse_integer), not(one_of(".eE,"))), |(start, end, step)| {
+            GenRange::Integer(start, end.unwrap_or(Integer::MAX), step.unwrap_or(1))
+        }),
+        map_res_failure(range_parts(parse_float, parse_float), |(start, end, step)| {
+            GenRange::new_float(start, end.unwrap_or(Float::INFINITY), step.unwrap_or(1.0))
+        }),
+        map(range_parts(parse_gen_char, parse_integer), |(start, end, step)| {
+            GenRange::Char(start, end.unwrap_or(char::MAX), step.unwrap_or(1))
+        }),
+    ))
+    .parse(input)
+}
+
+/// 解析范围的各个部分`<start>[,[<end>][,<step>]]`，步长不能为0。
+fn range_parts<'a, T, S: Default + PartialEq>(
+    value: fn(&'a str) -> IResult<&'a str, T, RpParseErr<'a>>, step: fn(&'a str) -> IResult<&'a str, S, RpParseErr<'a>>,
+) -> impl Parser<&'a str, Output = (T, Option<T>, Option<S>), Error = RpParseErr<'a>> {
     map(
         (
-            context("<start>", parse_integer), // 必选起始值
+            context("<start>", value), // 必选起始值
             opt(preceded(
                 char(','), // 结束值分隔符
                 (
-                    opt(context("<end>", parse_integer)), //可选结束值
-                    opt(preceded(char(','), verify(context("<step>", parse_integer), |s| *s != 0))), // 可选步长
+                    opt(context("<end>", value)), //可选结束值
+                    opt(preceded(char(','), verify(context("<step>", step), |s| *s != S::default()))), // 可选步长
                 ),
             )),
         ),
-        |(start, end_and_step_opt)| {
-            if let Some((end_opt, step_opt)) = end_and_step_opt {
-                (start, end_opt.unwrap_or(Integer::MAX), step_opt.unwrap_or(1))
-            } else {
-                (start, Integer::MAX, 1)
-            }
+        |(start, end_and_step_opt)| match end_and_step_opt {
+            Some((end_opt, step_opt)) => (start, end_opt, step_opt),
+            None => (start, None, None),
         },
     )
+}
+
+/// 解析`:gen`的字符，可以使用单引号或双引号包裹。
+fn parse_gen_char(input: &str) -> IResult<&str, char, RpParseErr<'_>> {
+    terminated(
+        alt((
+            delimited(char('\''), anychar, char('\'')),
+            delimited(char('"'), anychar, char('"')),
+            verify(anychar, |c| !c.is_whitespace() && !matches!(c, ',' | '\'' | '"')),
+        )),
+        not(satisfy(|c| !c.is_whitespace() && c != ',')), // 只能是单个字符
+    )
+    .parse(input)
+}
+
+fn parse_csv(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Csv",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":csv"), // 命令
+                    (
+                        context("<file>", preceded(space1, arg_exclude_cmd)), // 文件或标准输入
+                        opt(preceded(
+                            space1,
+                            map_res_failure(
+                                context(
+                                    "<delimiter>",
+                                    verify(arg_exclude_cmd, |d: &str| !d.eq_ignore_ascii_case("header")),
+                                ),
+                                |delimiter| parse_delimiter(&delimiter),
+                            ),
+                        )), // 可选分隔符
+                        opt(preceded(space1, terminated(tag_no_case("header"), arg_end))), // 可选表头
+                    ),
+                ),
+                context("(trailing_space1)", space1), // 结尾空格
+            ),
+            |(file, delimiter, header)| {
+                let file = if file.eq_ignore_ascii_case("stdin") { None } else { Some(file) };
+                Input::new_csv(file, delimiter.unwrap_or(DEFAULT_CSV_DELIMITER), header.is_some())
+            },
+        ),
+    )
+    .parse(input)
+}
+
+#[cfg(unix)]
+fn parse_socket(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Socket",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":socket"), // 命令
+                    (
+                        context("<path>", preceded(space1, arg_exclude_cmd)), // 套接字路径
+                        opt(preceded(space1, context("<count>", verify(usize, |count| *count > 0)))), // 可选的连接数量
+                    ),
+                ),
+                context("(trailing_space1)", space1), // 结尾空格
+            ),
+            |(path, count)| Input::new_socket(path, count),
+        ),
+    )
     .parse(input)
 }
 
@@ -122,10 +268,315 @@ fn parse_repeat(input: &str) -> InputIResult<'_> {
     .parse(input)
 }
 
+fn parse_exec(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Exec",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":exec"), // 命令
+                    (
+                        opt(preceded(space1, terminated(tag_no_case("stderr"), arg_end))), // 可选合并标准错误输出
+                        opt(preceded(space1, terminated(tag_no_case("check"), arg_end))),  // 可选检查退出状态
+                        arg1("<program>"),                                                 // 程序及参数
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(stderr, check, mut cmd_args)| {
+                let program = cmd_args.remove(0); // arg1保证至少有一个参数
+                Input::new_exec(program, cmd_args, stderr.is_some(), check.is_some())
+            },
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_env(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Env",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":env"),                                         // 命令
+                    opt(context("<prefix>", preceded(space1, arg_exclude_cmd))), // 可选变量名前缀
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            Input::new_env,
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_ls(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Ls",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":ls"), // 命令
+                    (
+                        context("<dir>", preceded(space1, arg_exclude_cmd)), // 目录
+                        opt(preceded(space1, terminated(tag_no_case("recursive"), arg_end))), // 可选递归
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(dir, recursive)| Input::new_ls(dir, recursive.is_some()),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_args(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Args",
+        map_res_failure(
+            terminated(
+                preceded(
+                    tag_no_case(":args"), // 命令
+                    (
+                        context("<value>", preceded(space1, arg_exclude_cmd)), // 需要拆分的值
+                        opt(context("<sep>", preceded(space1, arg_exclude_cmd))), // 可选分隔符
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(value, sep)| Input::new_args(value, sep),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_rand(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Rand",
+        map_res_failure(
+            terminated(
+                preceded(
+                    tag_no_case(":rand"), // 命令
+                    (
+                        opt(preceded(space1, context("<count>", terminated(usize, arg_end)))), // 可选数量
+                        opt(preceded(
+                            (space1, tag_no_case("seed"), space1),
+                            context("<seed>", terminated(u64, arg_end)),
+                        )), // 可选种子
+                        preceded(space1, parse_rand_kind),                                     // 随机数据种类
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(count, seed, kind)| Ok(Input::new_rand(kind?, count, seed)),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_rand_kind(input: &str) -> IResult<&str, Result<RandKind, RpErr>, RpParseErr<'_>> {
+    context(
+        "<kind>",
+        alt((
+            map(preceded((tag_no_case("int"), space1), context("<range>", parse_int_pair)), |(min, max)| {
+                RandKind::new_int(min, max)
+            }),
+            map(preceded((tag_no_case("float"), space1), context("<range>", parse_float_pair)), |(min, max)| {
+                RandKind::new_float(min, max)
+            }),
+            map(
+                preceded(
+                    (tag_no_case("str"), space1),
+                    (
+                        context("<len>", parse_len_range),                            // 长度范围
+                        opt(preceded(space1, context("<charset>", arg_exclude_cmd))), // 可选字符集
+                    ),
+                ),
+                |((min_len, max_len), charset)| RandKind::new_str(min_len, max_len, charset),
+            ),
+            map(terminated(tag_no_case("uuid"), arg_end), |_| Ok(RandKind::Uuid)),
+            map(preceded(tag_no_case("pick"), arg1("<value>")), |values| Ok(RandKind::Pick(values))),
+        )),
+    )
+    .parse(input)
+}
+
+fn parse_dates(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Dates",
+        map_res_failure(
+            terminated(
+                preceded(
+                    tag_no_case(":dates"), // 命令
+                    (
+                        context("<start>", preceded(space1, arg_exclude_cmd)), // 起始日期时间
+                        context("<end>", preceded(space1, arg_exclude_cmd)),   // 结束日期时间
+                        preceded(space1, parse_date_step),                     // 步长
+                        opt(preceded(
+                            space1,
+                            context(
+                                "<pattern>",
+                                verify(arg_exclude_cmd, |pattern: &str| !pattern.eq_ignore_ascii_case("fmt")),
+                            ),
+                        )), // 可选日期时间格式
+                        parse_combine_fmt,                                     // 可选格式化字符串
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(start, end, step, pattern, fmt)| {
+                let start = parse_datetime("start", &start)?;
+                let end = parse_datetime("end", &end)?;
+                if let Some(pattern) = &pattern {
+                    check_pattern(pattern)?;
+                }
+                Ok(Input::new_dates(start, end, step, pattern, fmt))
+            },
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_product(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Product",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":product"), // 命令
+                    (
+                        many1(preceded(
+                            space1,
+                            context("<list>", verify(arg_exclude_cmd, |list: &str| !list.eq_ignore_ascii_case("fmt"))),
+                        )), // 候选值列表
+                        parse_combine_fmt, // 可选格式化字符串
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(lists, fmt)| Input::new_product(lists.iter().map(|list| ValueList::parse(list)).collect(), fmt),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_permutations(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Permutations",
+        map(
+            terminated(
+                preceded(tag_no_case(":permutations"), parse_k_values_fmt(":permutations")),
+                context("(trailing_space1)", space1),
+            ),
+            |(k, values, fmt)| Input::new_permutations(k, values, fmt),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_combinations(input: &str) -> InputIResult<'_> {
+    context(
+        "Input::Combinations",
+        map(
+            terminated(
+                preceded(tag_no_case(":combinations"), parse_k_values_fmt(":combinations")),
+                context("(trailing_space1)", space1),
+            ),
+            |(k, values, fmt)| Input::new_combinations(k, values, fmt),
+        ),
+    )
+    .parse(input)
+}
+
+/// 解析排列或组合的参数` <k> <values>[ fmt <fmt>]`。
+fn parse_k_values_fmt<'a>(
+    cmd: &'static str,
+) -> impl Parser<&'a str, Output = (usize, Vec<String>, Option<String>), Error = RpParseErr<'a>> {
+    (
+        preceded(space1, map_res_failure(context("<k>", terminated(usize, arg_end)), move |k| check_k(cmd, k))), // 元素个数
+        map(preceded(space1, context("<values>", arg_exclude_cmd)), |values| split_values(&values)), // 候选值列表
+        parse_combine_fmt,                                                                           // 可选格式化字符串
+    )
+}
+
+/// 解析可选的` fmt <fmt>`。
+fn parse_combine_fmt(input: &str) -> IResult<&str, Option<String>, RpParseErr<'_>> {
+    opt(preceded((space1, tag_no_case("fmt"), space1), context("<fmt>", arg_exclude_cmd))).parse(input)
+}
+
+/// 解析日期时间步长`<n><unit>`，`<n>`必须为正整数。
+pub(in crate::parse) fn parse_date_step(input: &str) -> IResult<&str, DateStep, RpParseErr<'_>> {
+    context(
+        "<step>",
+        map_opt(
+            terminated(
+                (
+                    verify(u32, |n| *n > 0),
+                    alt((
+                        value((0, 1), tag_no_case("mo")),
+                        value((0, 12), tag_no_case("y")),
+                        value((604800, 0), tag_no_case("w")),
+                        value((86400, 0), tag_no_case("d")),
+                        value((3600, 0), tag_no_case("h")),
+                        value((60, 0), tag_no_case("m")),
+                        value((1, 0), tag_no_case("s")),
+                    )),
+                ),
+                arg_end,
+            ),
+            |(n, (seconds, months)): (u32, (i64, u32))| {
+                if months > 0 {
+                    n.checked_mul(months).map(DateStep::Months)
+                } else {
+                    Some(DateStep::Seconds(n as i64 * seconds))
+                }
+            },
+        ),
+    )
+    .parse(input)
+}
+
+/// 解析整数范围`<min>,<max>`。
+pub(in crate::parse) fn parse_int_pair(input: &str) -> IResult<&str, (Integer, Integer), RpParseErr<'_>> {
+    separated_pair(context("<min>", parse_integer), char(','), context("<max>", parse_integer)).parse(input)
+}
+
+/// 解析浮点数范围`<min>,<max>`。
+pub(in crate::parse) fn parse_float_pair(input: &str) -> IResult<&str, (Float, Float), RpParseErr<'_>> {
+    separated_pair(context("<min>", parse_float), char(','), context("<max>", parse_float)).parse(input)
+}
+
+/// 解析长度范围`<len>[,<max_len>]`。
+pub(in crate::parse) fn parse_len_range(input: &str) -> IResult<&str, (usize, Option<usize>), RpParseErr<'_>> {
+    (context("<len>", usize), opt(preceded(char(','), context("<max_len>", usize)))).parse(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_input(""), Ok(("", Input::new_std_in())));
+        assert_eq!(parse_input(":uniq "), Ok((":uniq ", Input::new_std_in())));
+        assert_eq!(parse_input(":of a :uniq "), Ok((":uniq ", Input::new_of(vec!["a".to_string()]))));
+        assert_eq!(
+            parse_input(":of header :file body.txt :of footer :uniq "),
+            Ok((
+                ":uniq ",
+                Input::new_cat(vec![
+                    Input::new_of(vec!["header".to_string()]),
+                    Input::new_file(vec!["body.txt".to_string()], false),
+                    Input::new_of(vec!["footer".to_string()]),
+                ])
+            ))
+        );
+        assert_eq!(
+            parse_input(":CAT :in :gen 1,3 "),
+            Ok(("", Input::new_cat(vec![Input::new_std_in(), Input::new_gen(GenRange::Integer(1, 3, 1), None)])))
+        );
+        assert_eq!(parse_input(":cat :in "), Ok(("", Input::new_cat(vec![Input::new_std_in()]))));
+    }
+
     #[test]
     fn test_parse_std_in() {
         assert_eq!(parse_std_in(":in "), Ok(("", Input::new_std_in())));
@@ -135,29 +586,50 @@ mod tests {
 
     #[test]
     fn test_parse_file() {
-        assert_eq!(parse_file(":file f.txt "), Ok(("", Input::new_file(vec!["f.txt".to_string()]))));
-        assert_eq!(parse_file(":file [ "), Ok(("", Input::new_file(vec!["[".to_string()]))));
-        assert_eq!(parse_file(":file ] "), Ok(("", Input::new_file(vec!["]".to_string()]))));
+        assert_eq!(parse_file(":file f.txt "), Ok(("", Input::new_file(vec!["f.txt".to_string()], false))));
+        assert_eq!(parse_file(":file [ "), Ok(("", Input::new_file(vec!["[".to_string()], false))));
+        assert_eq!(parse_file(":file ] "), Ok(("", Input::new_file(vec!["]".to_string()], false))));
         assert_eq!(
             parse_file(":file [ ] [] "),
-            Ok(("", Input::new_file(vec!["[".to_string(), "]".to_string(), "[]".to_string()])))
+            Ok(("", Input::new_file(vec!["[".to_string(), "]".to_string(), "[]".to_string()], false)))
         );
-        assert_eq!(parse_file(r#":file "f .txt" "#), Ok(("", Input::new_file(vec!["f .txt".to_string()]))));
-        assert_eq!(parse_file(":file f.txt "), Ok(("", Input::new_file(vec!["f.txt".to_string()]))));
+        assert_eq!(parse_file(r#":file "f .txt" "#), Ok(("", Input::new_file(vec!["f .txt".to_string()], false))));
+        assert_eq!(parse_file(":file f.txt "), Ok(("", Input::new_file(vec!["f.txt".to_string()], false))));
         assert_eq!(
             parse_file(r#":file f.txt "f .txt" "#),
-            Ok(("", Input::new_file(vec!["f.txt".to_string(), "f .txt".to_string()])))
+            Ok(("", Input::new_file(vec!["f.txt".to_string(), "f .txt".to_string()], false)))
         );
+        assert_eq!(parse_file(":file f.txt follow "), Ok(("", Input::new_file(vec!["f.txt".to_string()], true))));
+        assert_eq!(
+            parse_file(":file f1.txt f2.txt Follow "),
+            Ok(("", Input::new_file(vec!["f1.txt".to_string(), "f2.txt".to_string()], true)))
+        );
+        assert_eq!(parse_file(":file follow "), Ok(("", Input::new_file(vec!["follow".to_string()], false))));
         assert!(parse_file(":file ").is_err());
     }
 
     #[test]
-    #[cfg(windows)]
+    #[cfg(any(windows, target_os = "linux"))]
     fn test_parse_clip() {
         assert_eq!(parse_clip(":clip "), Ok(("", Input::new_clip())));
         assert!(parse_clip(":clip").is_err());
     }
 
+    #[test]
+    fn test_parse_text() {
+        assert_eq!(parse_text(r#":text "a\nb" :upper "#), Ok((":upper ", Input::new_text("a\nb".to_string()))));
+        assert_eq!(
+            parse_text(":text <<EOF\nline 1\n:line 2\nEOF\n:upper "),
+            Ok((":upper ", Input::new_text("line 1\n:line 2\n".to_string())))
+        );
+        assert_eq!(
+            parse_text(":text <<'EOF'\na\\tb\nEOF :upper "),
+            Ok((":upper ", Input::new_text("a\\tb\n".to_string())))
+        );
+        assert!(matches!(parse_text(":text <<EOF\na\n"), Err(nom::Err::Failure(_))));
+        assert!(parse_text(":text ").is_err());
+    }
+
     #[test]
     fn test_parse_of() {
         assert_eq!(parse_of(":of value "), Ok(("", Input::new_of(vec!["value".to_string()]))));
@@ -178,14 +650,41 @@ mod tests {
 
     #[test]
     fn test_parse_gen() {
-        assert_eq!(parse_gen(":gen 0          "), Ok(("", Input::new_gen(0, Integer::MAX, 1, None))));
-        assert_eq!(parse_gen(":gen 0,         "), Ok(("", Input::new_gen(0, Integer::MAX, 1, None))));
-        assert_eq!(parse_gen(":gen 0,10       "), Ok(("", Input::new_gen(0, 10, 1, None))));
-        assert_eq!(parse_gen(":gen 0,10,2     "), Ok(("", Input::new_gen(0, 10, 2, None))));
-        assert_eq!(parse_gen(":gen 0,,2       "), Ok(("", Input::new_gen(0, Integer::MAX, 2, None))));
-        assert_eq!(parse_gen(":gen 10,0       "), Ok(("", Input::new_gen(10, 0, 1, None))));
-        assert_eq!(parse_gen(":gen 0,10,-1    "), Ok(("", Input::new_gen(0, 10, -1, None))));
-        assert_eq!(parse_gen(":gen 0,10 n{v}  "), Ok(("", Input::new_gen(0, 10, 1, Some("n{v}".to_string())))));
+        assert_eq!(
+            parse_gen(":gen 0          "),
+            Ok(("", Input::new_gen(GenRange::Integer(0, Integer::MAX, 1), None)))
+        );
+        assert_eq!(
+            parse_gen(":gen 0,         "),
+            Ok(("", Input::new_gen(GenRange::Integer(0, Integer::MAX, 1), None)))
+        );
+        assert_eq!(parse_gen(":gen 0,10       "), Ok(("", Input::new_gen(GenRange::Integer(0, 10, 1), None))));
+        assert_eq!(parse_gen(":gen 0,10,2     "), Ok(("", Input::new_gen(GenRange::Integer(0, 10, 2), None))));
+        assert_eq!(
+            parse_gen(":gen 0,,2       "),
+            Ok(("", Input::new_gen(GenRange::Integer(0, Integer::MAX, 2), None)))
+        );
+        assert_eq!(parse_gen(":gen 10,0       "), Ok(("", Input::new_gen(GenRange::Integer(10, 0, 1), None))));
+        assert_eq!(parse_gen(":gen 0,10,-1    "), Ok(("", Input::new_gen(GenRange::Integer(0, 10, -1), None))));
+        assert_eq!(
+            parse_gen(":gen 0,10 n{v}  "),
+            Ok(("", Input::new_gen(GenRange::Integer(0, 10, 1), Some("n{v}".to_string()))))
+        );
+        assert_eq!(
+            parse_gen(":gen 0.5       "),
+            Ok(("", Input::new_gen(GenRange::Float(0.5, Float::INFINITY, 1.0), None)))
+        );
+        assert_eq!(parse_gen(":gen 0,1,0.1    "), Ok(("", Input::new_gen(GenRange::Float(0.0, 1.0, 0.1), None))));
+        assert_eq!(parse_gen(":gen 1e1,0,-2.5 "), Ok(("", Input::new_gen(GenRange::Float(10.0, 0.0, -2.5), None))));
+        assert_eq!(parse_gen(":gen a,z       "), Ok(("", Input::new_gen(GenRange::Char('a', 'z', 1), None))));
+        assert_eq!(parse_gen(":gen 'α','ω',-2 "), Ok(("", Input::new_gen(GenRange::Char('α', 'ω', -2), None))));
+        assert_eq!(parse_gen(r#":gen " ",       "#), Ok(("", Input::new_gen(GenRange::Char(' ', char::MAX, 1), None))));
+        assert!(parse_gen(":gen a,z,0.5 ").is_err());
+        assert!(parse_gen(":gen ab ").is_err());
+        assert!(matches!(
+            parse_gen(":gen 0,,-0.5 "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, RpErr::ArgParseErr { cmd: ":gen", .. }))))
+        ));
         assert!(parse_gen(":gen 0,10,0     ").is_err());
     }
 
@@ -194,4 +693,213 @@ mod tests {
         assert_eq!(parse_repeat(":repeat abc "), Ok(("", Input::new_repeat("abc".to_string(), None))));
         assert_eq!(parse_repeat(":repeat abc 10 "), Ok(("", Input::new_repeat("abc".to_string(), Some(10)))));
     }
+
+    #[test]
+    fn test_parse_csv() {
+        assert_eq!(parse_csv(":csv a.csv "), Ok(("", Input::new_csv(Some("a.csv".to_string()), b',', false))));
+        assert_eq!(parse_csv(r#":csv stdin "\t" header :uniq "#), Ok((":uniq ", Input::new_csv(None, b'\t', true))));
+        assert_eq!(parse_csv(":csv a.csv HEADER "), Ok(("", Input::new_csv(Some("a.csv".to_string()), b',', true))));
+        assert!(matches!(parse_csv(":csv a.csv ;; "), Err(nom::Err::Failure(_))));
+        assert!(parse_csv(":csv ").is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_parse_socket() {
+        assert_eq!(parse_socket(":socket rp.sock "), Ok(("", Input::new_socket("rp.sock".to_string(), None))));
+        assert_eq!(
+            parse_socket(":socket rp.sock 3 :uniq "),
+            Ok((":uniq ", Input::new_socket("rp.sock".to_string(), Some(3))))
+        );
+        assert_eq!(parse_socket(":socket rp.sock 0 "), Ok(("0 ", Input::new_socket("rp.sock".to_string(), None))));
+        assert!(parse_socket(":socket ").is_err());
+    }
+
+    #[test]
+    fn test_parse_exec() {
+        assert_eq!(parse_exec(":exec ls "), Ok(("", Input::new_exec("ls".to_string(), vec![], false, false))));
+        assert_eq!(
+            parse_exec(r#":exec ls -l "a b" :uniq "#),
+            Ok((":uniq ", Input::new_exec("ls".to_string(), vec!["-l".to_string(), "a b".to_string()], false, false)))
+        );
+        assert_eq!(
+            parse_exec(":exec stderr check cargo build "),
+            Ok(("", Input::new_exec("cargo".to_string(), vec!["build".to_string()], true, true)))
+        );
+        assert_eq!(
+            parse_exec(":exec stderrx "),
+            Ok(("", Input::new_exec("stderrx".to_string(), vec![], false, false)))
+        );
+        assert!(parse_exec(":exec ").is_err());
+        assert!(parse_exec(":exec stderr ").is_err());
+    }
+
+    #[test]
+    fn test_parse_env() {
+        assert_eq!(parse_env(":env "), Ok(("", Input::new_env(None))));
+        assert_eq!(parse_env(":env CARGO_ "), Ok(("", Input::new_env(Some("CARGO_".to_string())))));
+        assert_eq!(parse_env(":env :uniq "), Ok((":uniq ", Input::new_env(None))));
+        assert!(parse_env(":envx ").is_err());
+    }
+
+    #[test]
+    fn test_parse_ls() {
+        assert_eq!(parse_ls(":ls src "), Ok(("", Input::new_ls("src".to_string(), false))));
+        assert_eq!(parse_ls(":ls src RECURSIVE "), Ok(("", Input::new_ls("src".to_string(), true))));
+        assert_eq!(parse_ls(r#":ls "a dir" :uniq "#), Ok((":uniq ", Input::new_ls("a dir".to_string(), false))));
+        assert!(parse_ls(":ls ").is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(":args a:b "), Ok(("", Input::new_args("a:b".to_string(), None).unwrap())));
+        assert_eq!(
+            parse_args(":args a,b , "),
+            Ok(("", Input::new_args("a,b".to_string(), Some(",".to_string())).unwrap()))
+        );
+        assert_eq!(
+            parse_args(":args a:b : "),
+            Ok(("", Input::new_args("a:b".to_string(), Some(":".to_string())).unwrap()))
+        );
+        assert!(matches!(
+            parse_args(":args abc '' "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, RpErr::ArgParseErr { cmd: ":args", arg: "sep", .. }))))
+        ));
+        assert!(parse_args(":args ").is_err());
+    }
+
+    #[test]
+    fn test_parse_rand() {
+        assert_eq!(
+            parse_rand(":rand int 1,100 "),
+            Ok(("", Input::new_rand(RandKind::new_int(1, 100).unwrap(), None, None)))
+        );
+        assert_eq!(
+            parse_rand(":rand 10 seed 42 float -0.5,1 "),
+            Ok(("", Input::new_rand(RandKind::new_float(-0.5, 1.0).unwrap(), Some(10), Some(42))))
+        );
+        assert_eq!(
+            parse_rand(":rand 5 str 8 "),
+            Ok(("", Input::new_rand(RandKind::new_str(8, None, None).unwrap(), Some(5), None)))
+        );
+        assert_eq!(
+            parse_rand(":rand 5 str 4,8 a-f0-9 :uniq "),
+            Ok((
+                ":uniq ",
+                Input::new_rand(RandKind::new_str(4, Some(8), Some("a-f0-9".to_string())).unwrap(), Some(5), None)
+            ))
+        );
+        assert_eq!(parse_rand(":rand 0 uuid "), Ok(("", Input::new_rand(RandKind::Uuid, Some(0), None))));
+        assert_eq!(
+            parse_rand(":rand seed 1 pick a b :uniq "),
+            Ok((":uniq ", Input::new_rand(RandKind::Pick(vec!["a".to_string(), "b".to_string()]), None, Some(1))))
+        );
+        assert!(parse_rand(":rand ").is_err());
+        assert!(parse_rand(":rand uuidx ").is_err());
+        assert!(parse_rand(":rand pick ").is_err());
+        assert!(parse_rand(":rand int 1 ").is_err());
+        assert!(parse_rand(":rand seed uuid ").is_err());
+        assert!(matches!(
+            parse_rand(":rand int 3,1 "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, RpErr::ArgParseErr { cmd: ":rand", .. }))))
+        ));
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        let datetime = |s: &str| parse_datetime("start", s).unwrap();
+        assert_eq!(
+            parse_dates(":dates 2024-01-01 2024-01-31 1d "),
+            Ok((
+                "",
+                Input::new_dates(datetime("2024-01-01"), datetime("2024-01-31"), DateStep::Seconds(86400), None, None)
+            ))
+        );
+        assert_eq!(
+            parse_dates(r#":dates "2024-01-01 08:00" 2024-01-01T18:00 2h %H fmt "{v} {ts}" :uniq "#),
+            Ok((
+                ":uniq ",
+                Input::new_dates(
+                    datetime("2024-01-01 08:00"),
+                    datetime("2024-01-01 18:00"),
+                    DateStep::Seconds(7200),
+                    Some("%H".to_string()),
+                    Some("{v} {ts}".to_string())
+                )
+            ))
+        );
+        assert_eq!(
+            parse_dates(":dates 2024-01-01 2024-01-01 1d fmt p_{v} "),
+            Ok((
+                "",
+                Input::new_dates(
+                    datetime("2024-01-01"),
+                    datetime("2024-01-01"),
+                    DateStep::Seconds(86400),
+                    None,
+                    Some("p_{v}".to_string())
+                )
+            ))
+        );
+        assert!(parse_dates(":dates 2024-01-01 2024-01-31 ").is_err());
+        assert!(parse_dates(":dates 2024-01-01 2024-01-31 1x ").is_err());
+        assert!(matches!(
+            parse_dates(":dates 2024-13-01 2024-01-31 1d "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, RpErr::ArgParseErr { cmd: ":dates", arg: "start", .. }))))
+        ));
+    }
+
+    #[test]
+    fn test_parse_product() {
+        let list = |name: Option<&str>, values: &[&str]| {
+            ValueList::new(name.map(String::from), values.iter().map(|v| v.to_string()).collect())
+        };
+        assert_eq!(parse_product(":product a,b "), Ok(("", Input::new_product(vec![list(None, &["a", "b"])], None))));
+        assert_eq!(
+            parse_product(r#":product host=x,y port=80,443 FMT "{host}:{1}" :limit 1 "#),
+            Ok((
+                ":limit 1 ",
+                Input::new_product(
+                    vec![list(Some("host"), &["x", "y"]), list(Some("port"), &["80", "443"])],
+                    Some("{host}:{1}".to_string())
+                )
+            ))
+        );
+        assert!(parse_product(":product ").is_err());
+        assert!(parse_product(":product fmt {0} ").is_err());
+        assert_eq!(Ok("fmt "), parse_product(":product a,b fmt ").map(|(remaining, _)| remaining));
+    }
+
+    #[test]
+    fn test_parse_permutations_and_combinations() {
+        let values = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            parse_permutations(":permutations 2 a,b,c "),
+            Ok(("", Input::new_permutations(2, values(&["a", "b", "c"]), None)))
+        );
+        assert_eq!(
+            parse_combinations(":combinations 2 a,b,c fmt {0}-{1} :uniq "),
+            Ok((":uniq ", Input::new_combinations(2, values(&["a", "b", "c"]), Some("{0}-{1}".to_string()))))
+        );
+        assert!(parse_permutations(":permutations a,b,c ").is_err());
+        assert!(matches!(
+            parse_permutations(":permutations 0 a,b,c "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, RpErr::ArgParseErr { cmd: ":permutations", arg: "k", .. }))))
+        ));
+        assert!(parse_combinations(":combinations 2 ").is_err());
+    }
+
+    #[test]
+    fn test_parse_date_step() {
+        assert_eq!(parse_date_step("15s"), Ok(("", DateStep::Seconds(15))));
+        assert_eq!(parse_date_step("15m"), Ok(("", DateStep::Seconds(900))));
+        assert_eq!(parse_date_step("2H"), Ok(("", DateStep::Seconds(7200))));
+        assert_eq!(parse_date_step("1d"), Ok(("", DateStep::Seconds(86400))));
+        assert_eq!(parse_date_step("2w "), Ok((" ", DateStep::Seconds(1209600))));
+        assert_eq!(parse_date_step("3mo"), Ok(("", DateStep::Months(3))));
+        assert_eq!(parse_date_step("1y"), Ok(("", DateStep::Months(12))));
+        assert!(parse_date_step("0d").is_err());
+        assert!(parse_date_step("d").is_err());
+        assert!(parse_date_step("1dx").is_err());
+    }
 }
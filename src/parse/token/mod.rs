@@ -10,6 +10,8 @@ use crate::err::RpErr;
 use crate::input::Input;
 use crate::op::Op;
 use crate::output::Output;
+use crate::output::compress::Compression;
+use crate::output::line_ending::LineEnding;
 
 type ParseResult<'a, T> = Result<(&'a str, T), RpErr>;
 type ParseWithConfigsResult<'a> = ParseResult<'a, (Vec<Config>, Input, Vec<Op>, Output)>;
@@ -20,13 +22,13 @@ use crate::parse::token::op::parse_ops;
 use crate::parse::token::output::parse_out;
 use nom::branch::alt;
 use nom::bytes::complete::{escaped, take_while1};
-use nom::bytes::complete::{tag_no_case, take_while};
-use nom::character::complete::{anychar, char, usize};
+use nom::bytes::complete::{tag, tag_no_case, take_while};
+use nom::character::complete::{anychar, char, digit1, usize};
 use nom::character::complete::{none_of, space1};
-use nom::combinator::{eof, map, map_res, opt, peek, recognize, value, verify};
-use nom::error::context;
+use nom::combinator::{eof, map, map_opt, map_res, opt, peek, recognize, value, verify};
+use nom::error::{ContextError, ErrorKind, ParseError, context};
 use nom::multi::{fold_many1, many_till};
-use nom::sequence::{delimited, preceded, separated_pair};
+use nom::sequence::{delimited, preceded, separated_pair, terminated};
 use nom::{ExtendInto, IResult, Parser};
 use std::borrow::Cow;
 use std::str::FromStr;
@@ -80,13 +82,54 @@ pub(in crate::parse) fn parse_num(input: &str) -> IResult<&str, Num, RpParseErr<
 
 fn general_file_info<'a>(
     optional: bool,
-) -> impl Parser<&'a str, Output = (String, Option<&'a str>, Option<&'a str>), Error = RpParseErr<'a>> {
+) -> impl Parser<&'a str, Output = (String, Option<&'a str>, LineEnding), Error = RpParseErr<'a>> {
     (
         context("<file>", if optional { arg_exclude_cmd } else { arg }), // 文件
         opt(preceded(space1, tag_no_case("append"))),                    // 是否追加
-        opt(preceded(space1, alt((tag_no_case("lf"), tag_no_case("crlf"))))), // 换行符
+        line_ending,                                                     // 结束符
     )
 }
+
+/// 解析可选的结束符` lf|crlf|nul|none|eol <terminator>`，未指定时为`LF`。
+fn line_ending(input: &str) -> IResult<&str, LineEnding, RpParseErr<'_>> {
+    map(
+        opt(preceded(
+            space1,
+            alt((
+                map(
+                    preceded((tag_no_case("eol"), space1), context("<terminator>", arg_exclude_cmd)),
+                    LineEnding::Custom,
+                ),
+                map_opt(
+                    terminated(
+                        alt((tag_no_case("crlf"), tag_no_case("lf"), tag_no_case("nul"), tag_no_case("none"))),
+                        arg_end,
+                    ),
+                    LineEnding::from_keyword,
+                ),
+            )),
+        )),
+        Option::unwrap_or_default,
+    )
+    .parse(input)
+}
+
+/// 构造一个解析器，解析可选的压缩格式` gzip|zstd[ <level>]`，压缩级别超出范围时解析失败。
+fn compression<'a>(cmd: &'static str) -> impl Parser<&'a str, Output = Option<Compression>, Error = RpParseErr<'a>> {
+    let level = || opt(preceded(space1, context("<level>", terminated(digit1, arg_end))));
+    opt(preceded(
+        space1,
+        alt((
+            map_res_failure(preceded(terminated(tag_no_case("gzip"), arg_end), level()), move |level| {
+                Compression::new_gzip(cmd, level)
+            }),
+            map_res_failure(preceded(terminated(tag_no_case("zstd"), arg_end), level()), move |level| {
+                Compression::new_zstd(cmd, level)
+            }),
+        )),
+    ))
+}
+
 /// 构造一个解析器，解析`cmd arg [arg ][arg ][...]`，即解析至少一个参数直到遇到下一个冒号命令，
 /// 如果参数以冒号开头需要使用`\:`代替开头的`:`。
 fn cmd_arg1<'a>(
@@ -95,19 +138,24 @@ fn cmd_arg1<'a>(
     preceded(
         // 丢弃：命令标记
         tag_no_case(cmd_name),
-        map(
-            context(
-                arg_name,
-                verify(
-                    many_till(
-                        preceded(space1, arg_exclude_cmd), // 空格、参数
-                        peek((space1, alt((cmd, eof)))),   // 直到下一个命令，但不消耗此命令，或达到结尾，忽略结果
-                    ),
-                    |(args, _)| !args.is_empty(), // 验证：参数非空
+        arg1(arg_name),
+    )
+}
+
+/// 构造一个解析器，解析` arg [arg ][arg ][...]`，即解析至少一个以空格开头的参数直到遇到下一个冒号命令。
+fn arg1<'a>(arg_name: &'static str) -> impl Parser<&'a str, Output = Vec<String>, Error = RpParseErr<'a>> {
+    map(
+        context(
+            arg_name,
+            verify(
+                many_till(
+                    preceded(space1, arg_exclude_cmd), // 空格、参数
+                    peek((space1, alt((cmd, eof)))),   // 直到下一个命令，但不消耗此命令，或达到结尾，忽略结果
                 ),
+                |(args, _)| !args.is_empty(), // 验证：参数非空
             ),
-            |(args, _)| args,
         ),
+        |(args, _)| args,
     )
 }
 
@@ -225,6 +273,48 @@ pub(in crate::parse) fn escape(input: &str) -> IResult<&str, String, RpParseErr<
     escaped_trans(none_of("\\"), '\\', normal_escape).parse(input)
 }
 
+/// 解析类Shell的Here Document：`<<DELIM`后换行，之后的各行为内容，直到某一行以`DELIM`开头且其后为空白或结尾，
+/// 分隔符所在行的剩余部分留待后续解析。分隔符以单引号包围时内容原样保留，否则处理转义。
+pub(in crate::parse) fn heredoc(input: &str) -> IResult<&str, String, RpParseErr<'_>> {
+    let (body, (quoted, delimiter)) = context(
+        "heredoc_delimiter",
+        preceded(
+            tag("<<"),
+            terminated(
+                alt((
+                    map(delimited(char('\''), take_while1(|c| c != '\'' && c != '\n'), char('\'')), |d| (true, d)),
+                    map(take_while1(|c: char| !c.is_whitespace() && c != '\''), |d| (false, d)),
+                )),
+                (opt(char('\r')), char('\n')),
+            ),
+        ),
+    )
+    .parse(input)?;
+    let mut pos = 0;
+    loop {
+        let line = &body[pos..];
+        if let Some(rest) = line.strip_prefix(delimiter)
+            && rest.chars().next().is_none_or(char::is_whitespace)
+        {
+            let content = &body[..pos];
+            let content = if quoted {
+                content.to_string()
+            } else {
+                escape(content).map_or_else(|_| content.to_string(), |(_, content)| content)
+            };
+            return Ok((rest, content));
+        }
+        match line.find('\n') {
+            Some(idx) => pos += idx + 1,
+            None => {
+                // 已经识别为Here Document，缺少结束分隔符时不再尝试其他解析方式
+                let err = RpParseErr::from_error_kind(line, ErrorKind::TakeUntil);
+                return Err(nom::Err::Failure(RpParseErr::add_context(line, "heredoc_end", err)));
+            }
+        }
+    }
+}
+
 /// `nom::bytes::complete::escaped_transform`的优化版本，escaped_transform处理不在转义范围内的反斜杠字符时如果需要
 /// 保留原本的字符内容，第三个参数需要返回String类型，导致许多零碎的String碎片，此优化版本一次性解析后手动替换转义
 /// 字符，原样保留非转义内容，并且一次性申请String对象。
@@ -316,6 +406,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_heredoc() {
+        assert_eq!(heredoc("<<EOF\na\\tb\n c\nEOF :upper "), Ok((" :upper ", "a\tb\n c\n".to_string())));
+        assert_eq!(heredoc("<<'EOF'\r\na\\tb\r\nEOF"), Ok(("", "a\\tb\r\n".to_string())));
+        assert_eq!(heredoc("<<END\nEND\n:upper "), Ok(("\n:upper ", "".to_string())));
+        assert_eq!(heredoc("<<END\nENDING\nEND "), Ok((" ", "ENDING\n".to_string())));
+        assert!(matches!(heredoc("<<END\na\n"), Err(nom::Err::Failure(_))));
+        assert!(matches!(heredoc("<<END a"), Err(nom::Err::Error(_))));
+        assert!(matches!(heredoc("END"), Err(nom::Err::Error(_))));
+    }
+
     #[test]
     fn test_escape_string() {
         assert_eq!(escape(""), Ok(("", "".to_owned())));
@@ -1,20 +1,23 @@
 use crate::err::RpErr;
+use crate::op::json::{JsonPath, JsonPolicy};
 use crate::op::trim::{TrimArg, TrimPos};
+use crate::op::zip::{DEFAULT_ZIP_DELIMITER, ZipJoin, ZipPolicy};
 use crate::op::{CaseArg, JoinInfo, Op, PeekArg, RegArg, SortBy, TakeDropMode};
 use crate::parse::token::condition::parse_cond;
+use crate::parse::token::output::parse_to;
 use crate::parse::token::{
-    arg, arg_end, arg_exclude_cmd, general_file_info, map_res_failure, parse_arg_as, parse_usize_range,
+    arg, arg_end, arg_exclude_cmd, compression, general_file_info, map_res_failure, parse_arg_as, parse_usize_range,
 };
-use crate::parse::{OpIResult, OpsIResult};
+use crate::parse::{OpIResult, OpsIResult, RpParseErr};
 use crate::{Float, Integer};
-use nom::Parser;
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
 use nom::character::complete::{space1, usize};
-use nom::combinator::{map, opt, value, verify};
+use nom::combinator::{map, not, opt, value, verify};
 use nom::error::context;
 use nom::multi::{many0, many1};
 use nom::sequence::{delimited, preceded, terminated};
+use nom::{IResult, Parser};
 
 // TODO 2026-01-22 02:10 改造token解析结果，支持传递RpErr，补充相关UT
 pub(in crate::parse) fn parse_ops(input: &str) -> OpsIResult<'_> {
@@ -22,10 +25,13 @@ pub(in crate::parse) fn parse_ops(input: &str) -> OpsIResult<'_> {
         "Op",
         many0(alt((
             parse_peek,
+            parse_tee,
             parse_case,
             parse_replace,
             parse_trim,
             parse_reg,
+            parse_fmt,
+            parse_json,
             parse_slice,
             parse_uniq,
             parse_sum,
@@ -33,29 +39,49 @@ pub(in crate::parse) fn parse_ops(input: &str) -> OpsIResult<'_> {
             parse_take_drop,
             parse_count,
             parse_sort,
+            parse_zip,
+            parse_interleave,
         ))),
     )
     .parse(input)
 }
 
+fn parse_tee(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Tee",
+        map_res_failure(
+            preceded(
+                (tag_no_case(":tee"), space1), // 丢弃：命令+空格
+                context("<output>", parse_to), // 输出命令，包含结尾空格
+            ),
+            Op::new_tee,
+        ),
+    )
+    .parse(input)
+}
+
 fn parse_peek(input: &str) -> OpIResult<'_> {
     context(
         "Op::Peek",
         map(
             terminated(
                 preceded(
-                    tag_no_case(":peek"),                           // 丢弃命令
-                    opt(preceded(space1, general_file_info(true))), // 可选文件信息
+                    tag_no_case(":peek"), // 丢弃命令
+                    (
+                        opt(preceded(
+                            (space1, not((tag_no_case("fmt"), space1))),
+                            (general_file_info(true), compression(":peek")),
+                        )), // 可选文件信息
+                        opt(preceded((space1, tag_no_case("fmt"), space1), context("<fmt>", arg_exclude_cmd))), // 可选格式化字符串
+                    ),
                 ),
                 context("(trailing_space1)", space1), // 结尾空格
             ),
-            |file_info| match file_info {
-                Some((file, append_opt, postfix_opt)) => Op::Peek(PeekArg::File {
-                    file,
-                    append: append_opt.is_some(),
-                    crlf: postfix_opt.map(|s| s.eq_ignore_ascii_case("crlf")),
-                }),
-                None => Op::Peek(PeekArg::StdOut),
+            |(file_info, fmt)| match file_info {
+                Some(((file, append_opt, ending), compression)) => {
+                    Op::new_peek(PeekArg::File { file, append: append_opt.is_some(), ending, compression }, fmt)
+                }
+                None => Op::new_peek(PeekArg::StdOut, fmt),
             },
         ),
     )
@@ -168,6 +194,23 @@ fn parse_reg(input: &str) -> OpIResult<'_> {
     .parse(input)
 }
 
+fn parse_fmt(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Fmt",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":fmt"),                                 // 命令
+                    context("<fmt>", preceded(space1, arg_exclude_cmd)), // 格式化字符串
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |fmt| Op::Fmt { fmt },
+        ),
+    )
+    .parse(input)
+}
+
 fn parse_slice(input: &str) -> OpIResult<'_> {
     context(
         "Op::Slice",
@@ -377,10 +420,144 @@ fn parse_sort(input: &str) -> OpIResult<'_> {
     .parse(input)
 }
 
+fn parse_json(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Json",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":json"), // 命令
+                    (
+                        preceded(
+                            space1,
+                            map_res_failure(context("<path>", arg_exclude_cmd), |path| JsonPath::parse(&path)),
+                        ), // 路径
+                        opt(preceded(
+                            space1,
+                            alt((
+                                value(JsonPolicy::Drop, terminated(tag_no_case("drop"), arg_end)),
+                                value(JsonPolicy::Keep, terminated(tag_no_case("keep"), arg_end)),
+                                value(JsonPolicy::Error, terminated(tag_no_case("error"), arg_end)),
+                            )),
+                        )), // 可选策略
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(path, policy)| Op::new_json(path, policy.unwrap_or(JsonPolicy::Error)),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_zip(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Zip",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":zip"), // 命令
+                    (
+                        context("<file>", preceded(space1, arg_exclude_cmd)), // 文件
+                        opt(alt((
+                            map(
+                                preceded((space1, tag_no_case("fmt"), space1), context("<fmt>", arg_exclude_cmd)),
+                                ZipJoin::Fmt,
+                            ),
+                            map(
+                                preceded(
+                                    space1,
+                                    context("<delimiter>", verify(arg_exclude_cmd, |d: &str| !is_zip_keyword(d))),
+                                ),
+                                ZipJoin::Delimiter,
+                            ),
+                        ))), // 可选分隔符或格式化字符串
+                        parse_zip_policy,                                     // 可选策略
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(file, join, policy)| {
+                Op::new_zip(file, join.unwrap_or(ZipJoin::Delimiter(DEFAULT_ZIP_DELIMITER.to_string())), policy)
+            },
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_interleave(input: &str) -> OpIResult<'_> {
+    context(
+        "Op::Interleave",
+        map(
+            terminated(
+                preceded(
+                    tag_no_case(":interleave"), // 命令
+                    (
+                        context("<file>", preceded(space1, arg_exclude_cmd)), // 文件
+                        parse_zip_policy,                                     // 可选策略
+                    ),
+                ),
+                context("(trailing_space1)", space1),
+            ),
+            |(file, policy)| Op::new_interleave(file, policy),
+        ),
+    )
+    .parse(input)
+}
+
+/// 解析可选的` stop|pad[ <fill>]|error`，未指定时为`stop`。
+fn parse_zip_policy(input: &str) -> IResult<&str, ZipPolicy, RpParseErr<'_>> {
+    map(
+        opt(preceded(
+            space1,
+            alt((
+                value(ZipPolicy::Stop, terminated(tag_no_case("stop"), arg_end)),
+                map(
+                    preceded(
+                        terminated(tag_no_case("pad"), arg_end),
+                        opt(preceded(space1, context("<fill>", arg_exclude_cmd))),
+                    ),
+                    |fill| ZipPolicy::Pad(fill.unwrap_or_default()),
+                ),
+                value(ZipPolicy::Error, terminated(tag_no_case("error"), arg_end)),
+            )),
+        )),
+        |policy| policy.unwrap_or(ZipPolicy::Stop),
+    )
+    .parse(input)
+}
+
+/// `:zip`的分隔符不能与关键字相同。
+pub(in crate::parse) fn is_zip_keyword(arg: &str) -> bool {
+    ["fmt", "stop", "pad", "error"].iter().any(|keyword| arg.eq_ignore_ascii_case(keyword))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::condition::{Condition, Select};
+    use crate::output::Output;
+    use crate::output::compress::Compression;
+    use crate::output::line_ending::LineEnding;
+
+    #[test]
+    fn test_parse_tee() {
+        assert_eq!(
+            parse_tee(":tee :to file all.txt :uniq "),
+            Ok((
+                ":uniq ",
+                Op::new_tee(Output::new_file("all.txt".to_string(), false, false, LineEnding::default(), None))
+                    .unwrap()
+            ))
+        );
+        assert_eq!(parse_tee(":tee :to out :to clip "), Ok((":to clip ", Op::new_tee(Output::new_std_out()).unwrap())));
+        assert!(matches!(
+            parse_tee(":tee :to inplace :uniq "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, RpErr::ArgParseErr { cmd: ":tee", arg: "output", .. }))))
+        ));
+        assert!(parse_tee(":tee :uniq ").is_err());
+        assert!(parse_tee(":tee ").is_err());
+    }
 
     #[test]
     fn test_parse_case() {
@@ -551,6 +728,30 @@ mod tests {
         assert!(parse_trim(":rtrimr ").is_err());
     }
 
+    #[test]
+    fn test_parse_fmt() {
+        assert_eq!(parse_fmt(":fmt {v} "), Ok(("", Op::Fmt { fmt: "{v}".to_string() })));
+        assert_eq!(
+            parse_fmt(r#":fmt "{file}:{line}:{v}" :uniq "#),
+            Ok((":uniq ", Op::Fmt { fmt: "{file}:{line}:{v}".to_string() }))
+        );
+        assert!(parse_fmt(":fmt ").is_err());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let path = |path| JsonPath::parse(path).unwrap();
+        assert_eq!(parse_json(":json .a.b "), Ok(("", Op::new_json(path(".a.b"), JsonPolicy::Error))));
+        assert_eq!(
+            parse_json(":json '.items[0]' drop :uniq "),
+            Ok((":uniq ", Op::new_json(path(".items[0]"), JsonPolicy::Drop)))
+        );
+        assert_eq!(parse_json(":json . KEEP "), Ok(("", Op::new_json(path("."), JsonPolicy::Keep))));
+        assert_eq!(parse_json(":json . error "), Ok(("", Op::new_json(path("."), JsonPolicy::Error))));
+        assert!(matches!(parse_json(":json a "), Err(nom::Err::Failure(_))));
+        assert!(parse_json(":json ").is_err());
+    }
+
     #[test]
     fn test_parse_reg() {
         let result1 = parse_reg(":reg [0-9] ");
@@ -602,29 +803,148 @@ mod tests {
 
     #[test]
     fn test_parse_peek() {
-        assert_eq!(parse_peek(":peek "), Ok(("", Op::Peek(PeekArg::StdOut))));
-        assert_eq!(parse_peek(":peek :abc "), Ok((":abc ", Op::Peek(PeekArg::StdOut))));
+        assert_eq!(parse_peek(":peek "), Ok(("", Op::new_peek(PeekArg::StdOut, None))));
+        assert_eq!(parse_peek(":peek :abc "), Ok((":abc ", Op::new_peek(PeekArg::StdOut, None))));
         assert_eq!(
             parse_peek(":peek out.txt "),
-            Ok(("", Op::Peek(PeekArg::File { file: "out.txt".to_string(), append: false, crlf: None })))
+            Ok((
+                "",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "out.txt".to_string(),
+                        append: false,
+                        ending: LineEnding::default(),
+                        compression: None
+                    },
+                    None
+                )
+            ))
         );
         assert_eq!(
             parse_peek(":peek out.txt append "),
-            Ok(("", Op::Peek(PeekArg::File { file: "out.txt".to_string(), append: true, crlf: None })))
+            Ok((
+                "",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "out.txt".to_string(),
+                        append: true,
+                        ending: LineEnding::default(),
+                        compression: None
+                    },
+                    None
+                )
+            ))
         );
         assert_eq!(
             parse_peek(":peek out.txt append crlf "),
-            Ok(("", Op::Peek(PeekArg::File { file: "out.txt".to_string(), append: true, crlf: Some(true) })))
+            Ok((
+                "",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "out.txt".to_string(),
+                        append: true,
+                        ending: LineEnding::CrLf,
+                        compression: None
+                    },
+                    None
+                )
+            ))
         );
         assert_eq!(
             parse_peek(":peek out.txt crlf "),
-            Ok(("", Op::Peek(PeekArg::File { file: "out.txt".to_string(), append: false, crlf: Some(true) })))
+            Ok((
+                "",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "out.txt".to_string(),
+                        append: false,
+                        ending: LineEnding::CrLf,
+                        compression: None
+                    },
+                    None
+                )
+            ))
         );
         assert_eq!(
             parse_peek(r#":peek "out .txt" "#),
-            Ok(("", Op::Peek(PeekArg::File { file: "out .txt".to_string(), append: false, crlf: None })))
+            Ok((
+                "",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "out .txt".to_string(),
+                        append: false,
+                        ending: LineEnding::default(),
+                        compression: None
+                    },
+                    None
+                )
+            ))
+        );
+        assert_eq!(parse_peek(":peek :replace crlf "), Ok((":replace crlf ", Op::new_peek(PeekArg::StdOut, None))));
+        assert_eq!(
+            parse_peek(r#":peek fmt "{line}:{v}" "#),
+            Ok(("", Op::new_peek(PeekArg::StdOut, Some("{line}:{v}".to_string()))))
+        );
+        assert_eq!(
+            parse_peek(":peek out.txt crlf fmt {v} "),
+            Ok((
+                "",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "out.txt".to_string(),
+                        append: false,
+                        ending: LineEnding::CrLf,
+                        compression: None
+                    },
+                    Some("{v}".to_string())
+                )
+            ))
+        );
+        assert_eq!(
+            parse_peek(":peek out.txt nul :uniq "),
+            Ok((
+                ":uniq ",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "out.txt".to_string(),
+                        append: false,
+                        ending: LineEnding::Nul,
+                        compression: None
+                    },
+                    None
+                )
+            ))
+        );
+        assert_eq!(
+            parse_peek(":peek out.log gzip 1 fmt {v} "),
+            Ok((
+                "",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "out.log".to_string(),
+                        append: false,
+                        ending: LineEnding::default(),
+                        compression: Some(Compression::Gzip(1))
+                    },
+                    Some("{v}".to_string())
+                )
+            ))
+        );
+        assert_eq!(
+            parse_peek(":peek fmt.txt "),
+            Ok((
+                "",
+                Op::new_peek(
+                    PeekArg::File {
+                        file: "fmt.txt".to_string(),
+                        append: false,
+                        ending: LineEnding::default(),
+                        compression: None
+                    },
+                    None
+                )
+            ))
         );
-        assert_eq!(parse_peek(":peek :replace crlf "), Ok((":replace crlf ", Op::Peek(PeekArg::StdOut))));
     }
 
     #[test]
@@ -671,4 +991,48 @@ mod tests {
         assert_eq!(parse_sort(":sort random "), Ok(("", Op::new_sort(SortBy::Random, false))));
         assert_eq!(parse_sort(":sort random desc "), Ok(("desc ", Op::new_sort(SortBy::Random, false))));
     }
+
+    #[test]
+    fn test_parse_zip() {
+        let tab = || ZipJoin::Delimiter(DEFAULT_ZIP_DELIMITER.to_string());
+        assert_eq!(parse_zip(":zip f.txt "), Ok(("", Op::new_zip("f.txt".to_string(), tab(), ZipPolicy::Stop))));
+        assert_eq!(
+            parse_zip(":zip f.txt , "),
+            Ok(("", Op::new_zip("f.txt".to_string(), ZipJoin::Delimiter(",".to_string()), ZipPolicy::Stop)))
+        );
+        assert_eq!(
+            parse_zip(r#":zip f.txt fmt "{0}={1}" pad NULL :uniq "#),
+            Ok((
+                ":uniq ",
+                Op::new_zip(
+                    "f.txt".to_string(),
+                    ZipJoin::Fmt("{0}={1}".to_string()),
+                    ZipPolicy::Pad("NULL".to_string())
+                )
+            ))
+        );
+        assert_eq!(
+            parse_zip(":zip f.txt PAD "),
+            Ok(("", Op::new_zip("f.txt".to_string(), tab(), ZipPolicy::Pad(String::new()))))
+        );
+        assert_eq!(
+            parse_zip(":zip f.txt , error "),
+            Ok(("", Op::new_zip("f.txt".to_string(), ZipJoin::Delimiter(",".to_string()), ZipPolicy::Error)))
+        );
+        assert_eq!(parse_zip(":zip f.txt stop "), Ok(("", Op::new_zip("f.txt".to_string(), tab(), ZipPolicy::Stop))));
+        assert!(parse_zip(":zip ").is_err());
+    }
+
+    #[test]
+    fn test_parse_interleave() {
+        assert_eq!(
+            parse_interleave(":interleave f.txt "),
+            Ok(("", Op::new_interleave("f.txt".to_string(), ZipPolicy::Stop)))
+        );
+        assert_eq!(
+            parse_interleave(":interleave f.txt pad - :uniq "),
+            Ok((":uniq ", Op::new_interleave("f.txt".to_string(), ZipPolicy::Pad("-".to_string()))))
+        );
+        assert!(parse_interleave(":interleave ").is_err());
+    }
 }
@@ -1,12 +1,17 @@
+use crate::err::RpErr;
 use crate::output::Output;
+use crate::output::atomic::check_atomic;
+use crate::output::split::{SplitBy, parse_size};
 use crate::parse::RpParseErr;
-use crate::parse::token::general_file_info;
+use crate::parse::token::{
+    arg_end, arg_exclude_cmd, arg1, compression, general_file_info, line_ending, map_res_failure,
+};
 use nom::IResult;
 use nom::Parser;
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
-use nom::character::complete::space1;
-use nom::combinator::{map, success};
+use nom::character::complete::{space1, usize};
+use nom::combinator::{map, opt, success, verify};
 use nom::error::context;
 use nom::sequence::{preceded, terminated};
 
@@ -16,22 +21,41 @@ pub(in crate::parse) fn parse_out(input: &str) -> OutputIResult<'_> {
     context(
         "Output",
         alt((
-            parse_to_std_out,
-            parse_to_file,
-            #[cfg(windows)]
-            parse_to_clip,
+            parse_to,
             context("Output::Out", map(success(()), |_| Output::new_std_out())), // 最后默认使用`Output::Out`
         )),
     )
     .parse(input)
 }
 
+/// 解析显式指定的输出命令。
+pub(in crate::parse) fn parse_to(input: &str) -> OutputIResult<'_> {
+    alt((
+        parse_to_std_out,
+        parse_to_file,
+        parse_to_split,
+        parse_to_in_place,
+        parse_to_table,
+        parse_to_json,
+        parse_to_csv,
+        parse_to_exec,
+        #[cfg(any(windows, target_os = "linux"))]
+        parse_to_clip,
+        #[cfg(unix)]
+        parse_to_socket,
+    ))
+    .parse(input)
+}
+
 fn parse_to_std_out(input: &str) -> OutputIResult<'_> {
     context(
         "Output::StdOut",
         map(
-            (tag_no_case(":to"), space1, tag_no_case("out"), space1), // 命令
-            |_| Output::new_std_out(),
+            terminated(
+                preceded((tag_no_case(":to"), space1, tag_no_case("out")), line_ending), // 命令、结束符
+                space1,                                                                  // 丢弃：结尾空格
+            ),
+            Output::new_std_out_with,
         ),
     )
     .parse(input)
@@ -40,23 +64,204 @@ fn parse_to_std_out(input: &str) -> OutputIResult<'_> {
 fn parse_to_file(input: &str) -> OutputIResult<'_> {
     context(
         "Output::File",
+        terminated(
+            preceded(
+                (tag_no_case(":to"), space1, tag_no_case("file")), // 命令
+                map_res_failure(
+                    (
+                        preceded(space1, general_file_info(false)),
+                        compression(":to file"),
+                        opt(preceded(space1, terminated(tag_no_case("atomic"), arg_end))),
+                    ),
+                    |((file, append_opt, ending), compression, atomic_opt)| {
+                        let append = append_opt.is_some();
+                        let atomic = check_atomic(":to file", append, atomic_opt.is_some())?;
+                        Ok(Output::new_file(file, append, atomic, ending, compression))
+                    },
+                ),
+            ),
+            space1, // 丢弃：结尾空格
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_to_split(input: &str) -> OutputIResult<'_> {
+    context(
+        "Output::Split",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":to"), space1, tag_no_case("split")), // 命令
+                    (
+                        context("<pattern>", preceded(space1, arg_exclude_cmd)), // 文件路径格式
+                        preceded(
+                            space1,
+                            alt((
+                                map(
+                                    preceded(
+                                        (tag_no_case("lines"), space1),
+                                        context("<count>", terminated(verify(usize, |c| *c > 0), arg_end)),
+                                    ),
+                                    SplitBy::Lines,
+                                ),
+                                map(
+                                    preceded(
+                                        (tag_no_case("bytes"), space1),
+                                        map_res_failure(context("<size>", arg_exclude_cmd), |size| parse_size(&size)),
+                                    ),
+                                    SplitBy::Bytes,
+                                ),
+                                preceded(
+                                    (tag_no_case("key"), space1),
+                                    map_res_failure(context("<regex>", arg_exclude_cmd), SplitBy::new_key),
+                                ),
+                            )),
+                        ), // 拆分方式
+                        opt(preceded(space1, terminated(tag_no_case("append"), arg_end))), // 是否追加
+                        line_ending,                                             // 结束符
+                    ),
+                ),
+                space1, // 丢弃：结尾空格
+            ),
+            |(pattern, by, append, ending)| Output::new_split(pattern, by, append.is_some(), ending),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_to_in_place(input: &str) -> OutputIResult<'_> {
+    context(
+        "Output::InPlace",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":to"), space1, tag_no_case("inplace")), // 命令
+                    (
+                        opt(preceded((space1, tag_no_case("backup"), space1), context("<suffix>", arg_exclude_cmd))), // 备份后缀
+                        line_ending, // 结束符
+                    ),
+                ),
+                space1, // 丢弃：结尾空格
+            ),
+            |(backup, ending)| Output::new_in_place(backup, ending),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_to_table(input: &str) -> OutputIResult<'_> {
+    context(
+        "Output::Table",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":to"), space1, tag_no_case("table")), // 命令
+                    (
+                        opt(preceded(
+                            space1,
+                            context(
+                                "<delimiter>",
+                                verify(arg_exclude_cmd, |d: &str| {
+                                    !d.eq_ignore_ascii_case("header") && !d.eq_ignore_ascii_case("border")
+                                }),
+                            ),
+                        )), // 可选分隔符
+                        opt(preceded(space1, terminated(tag_no_case("header"), arg_end))), // 可选表头
+                        opt(preceded(space1, terminated(tag_no_case("border"), arg_end))), // 可选边框
+                    ),
+                ),
+                space1, // 丢弃：结尾空格
+            ),
+            |(delimiter, header, border)| Output::new_table(delimiter, header.is_some(), border.is_some()),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_to_json(input: &str) -> OutputIResult<'_> {
+    context(
+        "Output::Json",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":to"), space1),
+                    (
+                        alt((terminated(tag_no_case("json"), arg_end), terminated(tag_no_case("ndjson"), arg_end))), // 命令
+                        opt(preceded(
+                            space1,
+                            context(
+                                "<delimiter>",
+                                verify(arg_exclude_cmd, |d: &str| !d.eq_ignore_ascii_case("header")),
+                            ),
+                        )), // 可选分隔符
+                        opt(preceded(space1, terminated(tag_no_case("header"), arg_end))), // 可选表头
+                    ),
+                ),
+                space1, // 丢弃：结尾空格
+            ),
+            |(cmd, delimiter, header): (&str, _, _)| {
+                if cmd.eq_ignore_ascii_case("json") {
+                    Output::new_json(delimiter, header.is_some())
+                } else {
+                    Output::new_nd_json(delimiter, header.is_some())
+                }
+            },
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_to_csv(input: &str) -> OutputIResult<'_> {
+    context(
+        "Output::Csv",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":to"), space1, terminated(tag_no_case("csv"), arg_end)), // 命令
+                    opt(preceded(space1, context("<delimiter>", arg_exclude_cmd))),        // 可选分隔符
+                ),
+                space1, // 丢弃：结尾空格
+            ),
+            Output::new_csv,
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_to_exec(input: &str) -> OutputIResult<'_> {
+    context(
+        "Output::Exec",
         map(
             terminated(
                 preceded(
-                    (tag_no_case(":to"), space1, tag_no_case("file")), // 命令
-                    preceded(space1, general_file_info(false)),
+                    (tag_no_case(":to"), space1, tag_no_case("exec")), // 命令
+                    (
+                        opt(preceded(
+                            (space1, tag_no_case("batch"), space1),
+                            map_res_failure(context("<count>", arg_exclude_cmd), |count| {
+                                count
+                                    .parse::<usize>()
+                                    .ok()
+                                    .filter(|count| *count > 0)
+                                    .ok_or(RpErr::MissingArg { cmd: ":to exec", arg: "count" })
+                            }),
+                        )), // 可选分批执行
+                        arg1("<program>"), // 程序及参数
+                    ),
                 ),
                 space1, // 丢弃：结尾空格
             ),
-            |(file, append_opt, postfix_opt): (String, Option<_>, Option<&str>)| {
-                Output::new_file(file, append_opt.is_some(), postfix_opt.map(|s| s.eq_ignore_ascii_case("crlf")))
+            |(batch, mut cmd_args)| {
+                let program = cmd_args.remove(0); // arg1保证至少有一个参数
+                Output::new_exec(program, cmd_args, batch)
             },
         ),
     )
     .parse(input)
 }
 
-#[cfg(windows)]
+#[cfg(any(windows, target_os = "linux"))]
 fn parse_to_clip(input: &str) -> OutputIResult<'_> {
     context(
         "Output::Clip",
@@ -64,11 +269,32 @@ fn parse_to_clip(input: &str) -> OutputIResult<'_> {
             preceded(
                 (tag_no_case(":to"), space1, tag_no_case("clip")), // 固定`:to clip`
                 terminated(
-                    nom::combinator::opt(preceded(space1, alt((tag_no_case("lf"), tag_no_case("crlf"))))), // 换行符
-                    space1,                                                                                // 结尾空格
+                    line_ending, // 结束符
+                    space1,      // 结尾空格
                 ),
             ), // 丢弃：`to clip `
-            |postfix_opt: Option<&str>| Output::new_clip(postfix_opt.map(|s| s.eq_ignore_ascii_case("crlf"))),
+            Output::new_clip,
+        ),
+    )
+    .parse(input)
+}
+
+#[cfg(unix)]
+fn parse_to_socket(input: &str) -> OutputIResult<'_> {
+    context(
+        "Output::Socket",
+        map(
+            terminated(
+                preceded(
+                    (tag_no_case(":to"), space1, tag_no_case("socket")), // 命令
+                    (
+                        context("<path>", preceded(space1, arg_exclude_cmd)), // 套接字路径
+                        line_ending,                                          // 结束符
+                    ),
+                ),
+                space1, // 丢弃：结尾空格
+            ),
+            |(path, ending)| Output::new_socket(path, ending),
         ),
     )
     .parse(input)
@@ -77,38 +303,190 @@ fn parse_to_clip(input: &str) -> OutputIResult<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::compress::Compression;
+    use crate::output::line_ending::LineEnding;
+
+    #[test]
+    fn test_parse_to_std_out() {
+        assert_eq!(parse_out(":to out "), Ok(("", Output::new_std_out())));
+        assert_eq!(parse_out(":to out NUL "), Ok(("", Output::new_std_out_with(LineEnding::Nul))));
+        assert_eq!(parse_out(":to out none "), Ok(("", Output::new_std_out_with(LineEnding::Empty))));
+        assert_eq!(
+            parse_out(r#":to out eol ", " "#),
+            Ok(("", Output::new_std_out_with(LineEnding::Custom(", ".to_string()))))
+        );
+        assert_eq!(
+            parse_out(r":to out eol \0\n "),
+            Ok(("", Output::new_std_out_with(LineEnding::Custom("\0\n".to_string()))))
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_parse_to_socket() {
+        assert_eq!(
+            parse_out(":to socket rp.sock "),
+            Ok(("", Output::new_socket("rp.sock".to_string(), LineEnding::default())))
+        );
+        assert_eq!(
+            parse_out(":to socket rp.sock crlf "),
+            Ok(("", Output::new_socket("rp.sock".to_string(), LineEnding::CrLf)))
+        );
+    }
+
+    #[test]
+    fn test_parse_to_split() {
+        assert_eq!(
+            parse_out(":to split part_{n}.txt lines 100 "),
+            Ok(("", Output::new_split("part_{n}.txt".to_string(), SplitBy::Lines(100), false, LineEnding::default())))
+        );
+        assert_eq!(
+            parse_out(":to split {n} bytes 1k append crlf "),
+            Ok(("", Output::new_split("{n}".to_string(), SplitBy::Bytes(1024), true, LineEnding::CrLf)))
+        );
+        assert_eq!(
+            parse_out(r#":to split "{key}.log" KEY '^(\S+)' lf "#),
+            Ok((
+                "",
+                Output::new_split(
+                    "{key}.log".to_string(),
+                    SplitBy::new_key(r"^(\S+)".to_string()).unwrap(),
+                    false,
+                    LineEnding::Lf
+                )
+            ))
+        );
+        assert!(matches!(parse_to_split(":to split {n} bytes 0 "), Err(nom::Err::Failure(_))));
+        assert!(matches!(parse_to_split(":to split {n} key ( "), Err(nom::Err::Failure(_))));
+        assert!(parse_to_split(":to split {n} lines 0 ").is_err());
+        assert!(parse_to_split(":to split {n} ").is_err());
+    }
+
+    #[test]
+    fn test_parse_to_table() {
+        assert_eq!(parse_out(":to table "), Ok(("", Output::new_table(None, false, false))));
+        assert_eq!(
+            parse_out(r#":to table "\t" HEADER border "#),
+            Ok(("", Output::new_table(Some("\t".to_string()), true, true)))
+        );
+        assert_eq!(parse_out(":to table border "), Ok(("", Output::new_table(None, false, true))));
+        assert_eq!(parse_out(":to table | header "), Ok(("", Output::new_table(Some("|".to_string()), true, false))));
+    }
+
+    #[test]
+    fn test_parse_to_encode() {
+        assert_eq!(parse_out(":to json "), Ok(("", Output::new_json(None, false))));
+        assert_eq!(parse_out(":to JSON , header "), Ok(("", Output::new_json(Some(",".to_string()), true))));
+        assert_eq!(parse_out(":to ndjson header "), Ok(("", Output::new_nd_json(None, true))));
+        assert_eq!(parse_out(r#":to ndjson "\t" "#), Ok(("", Output::new_nd_json(Some("\t".to_string()), false))));
+        assert_eq!(parse_out(":to csv "), Ok(("", Output::new_csv(None))));
+        assert_eq!(parse_out(":to csv | "), Ok(("", Output::new_csv(Some("|".to_string())))));
+    }
+
+    #[test]
+    fn test_parse_to_exec() {
+        assert_eq!(
+            parse_out(":to exec sort -u "),
+            Ok(("", Output::new_exec("sort".to_string(), vec!["-u".to_string()], None)))
+        );
+        assert_eq!(
+            parse_out(r#":to exec BATCH 3 echo "a b" "#),
+            Ok(("", Output::new_exec("echo".to_string(), vec!["a b".to_string()], Some(3))))
+        );
+        assert!(parse_to_exec(":to exec ").is_err());
+        assert!(matches!(parse_to_exec(":to exec batch 0 echo "), Err(nom::Err::Failure(_))));
+    }
+
+    #[test]
+    fn test_parse_to_in_place() {
+        assert_eq!(parse_out(":to inplace "), Ok(("", Output::new_in_place(None, LineEnding::default()))));
+        assert_eq!(
+            parse_out(":to inplace backup .bak crlf "),
+            Ok(("", Output::new_in_place(Some(".bak".to_string()), LineEnding::CrLf)))
+        );
+        assert_eq!(parse_out(":to  INPLACE  lf "), Ok(("", Output::new_in_place(None, LineEnding::Lf))));
+        assert_eq!(
+            parse_to_in_place(":to inplace backup "),
+            Ok(("backup ", Output::new_in_place(None, LineEnding::default())))
+        );
+    }
 
     #[test]
     fn test_parse_to_file() {
-        assert_eq!(parse_to_file(":to file out.txt "), Ok(("", Output::new_file("out.txt".to_string(), false, None))));
+        assert_eq!(
+            parse_to_file(":to file out.txt "),
+            Ok(("", Output::new_file("out.txt".to_string(), false, false, LineEnding::default(), None)))
+        );
         assert_eq!(
             parse_to_file(":to file out.txt append "),
-            Ok(("", Output::new_file("out.txt".to_string(), true, None)))
+            Ok(("", Output::new_file("out.txt".to_string(), true, false, LineEnding::default(), None)))
         );
         assert_eq!(
             parse_to_file(":to file out.txt append crlf "),
-            Ok(("", Output::new_file("out.txt".to_string(), true, Some(true))))
+            Ok(("", Output::new_file("out.txt".to_string(), true, false, LineEnding::CrLf, None)))
         );
         assert_eq!(
             parse_to_file(":to file out.txt crlf "),
-            Ok(("", Output::new_file("out.txt".to_string(), false, Some(true))))
+            Ok(("", Output::new_file("out.txt".to_string(), false, false, LineEnding::CrLf, None)))
         );
         assert_eq!(
             parse_to_file(r#":to file "out .txt" "#),
-            Ok(("", Output::new_file("out .txt".to_string(), false, None)))
+            Ok(("", Output::new_file("out .txt".to_string(), false, false, LineEnding::default(), None)))
+        );
+        assert_eq!(
+            parse_to_file(":to file out.log append gzip "),
+            Ok((
+                "",
+                Output::new_file("out.log".to_string(), true, false, LineEnding::default(), Some(Compression::Gzip(6)))
+            ))
+        );
+        assert_eq!(
+            parse_to_file(":to file out.log lf zstd 19 "),
+            Ok((
+                "",
+                Output::new_file("out.log".to_string(), false, false, LineEnding::Lf, Some(Compression::Zstd(19)))
+            ))
+        );
+        assert!(matches!(
+            parse_to_file(":to file out.log zstd 23 "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, RpErr::ArgParseErr { cmd: ":to file", arg: "level", .. }))))
+        ));
+        assert_eq!(
+            parse_to_file(":to file out.txt append nul gzip "),
+            Ok(("", Output::new_file("out.txt".to_string(), true, false, LineEnding::Nul, Some(Compression::Gzip(6)))))
+        );
+        assert_eq!(
+            parse_to_file(":to file out.txt eol ; "),
+            Ok(("", Output::new_file("out.txt".to_string(), false, false, LineEnding::Custom(";".to_string()), None)))
+        );
+        assert_eq!(
+            parse_to_file(":to file out.log zstd atomic "),
+            Ok((
+                "",
+                Output::new_file("out.log".to_string(), false, true, LineEnding::default(), Some(Compression::Zstd(3)))
+            ))
+        );
+        assert_eq!(
+            parse_to_file(":to file out.txt ATOMIC :uniq "),
+            Ok((":uniq ", Output::new_file("out.txt".to_string(), false, true, LineEnding::default(), None)))
         );
+        assert!(matches!(
+            parse_to_file(":to file out.txt append atomic "),
+            Err(nom::Err::Failure(RpParseErr::Rp((_, _, RpErr::ArgParseErr { cmd: ":to file", arg: "atomic", .. }))))
+        ));
         assert!(parse_to_file(":to").is_err());
         assert!(parse_to_file(":to file ").is_err());
         assert!(parse_to_file(":to file [").is_err());
     }
 
     #[test]
-    #[cfg(windows)]
+    #[cfg(any(windows, target_os = "linux"))]
     fn test_parse_to_clip() {
-        assert_eq!(parse_to_clip(":to clip "), Ok(("", Output::new_clip(None))));
-        assert_eq!(parse_to_clip(":to  clip  "), Ok(("", Output::new_clip(None))));
-        assert_eq!(parse_to_clip(":to clip lf "), Ok(("", Output::new_clip(Some(false)))));
-        assert_eq!(parse_to_clip(":to clip crlf "), Ok(("", Output::new_clip(Some(true)))));
+        assert_eq!(parse_to_clip(":to clip "), Ok(("", Output::new_clip(LineEnding::default()))));
+        assert_eq!(parse_to_clip(":to  clip  "), Ok(("", Output::new_clip(LineEnding::default()))));
+        assert_eq!(parse_to_clip(":to clip lf "), Ok(("", Output::new_clip(LineEnding::Lf))));
+        assert_eq!(parse_to_clip(":to clip crlf "), Ok(("", Output::new_clip(LineEnding::CrLf))));
+        assert_eq!(parse_to_clip(":to clip eol , "), Ok(("", Output::new_clip(LineEnding::Custom(",".to_string())))));
         assert!(parse_to_clip(":to ").is_err());
     }
 }
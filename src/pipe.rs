@@ -1,8 +1,57 @@
+use crate::Integer;
+use crate::err::RpErr;
+use crate::fmt::{FmtArg, fmt_positional_args};
+use std::cell::RefCell;
+use std::rc::Rc;
+
 pub(crate) struct Pipe {
     pub(crate) iter: Box<dyn Iterator<Item = String>>,
+    /// 当前数据的来源信息，数据源无法提供或者经过缓存、重排数据的操作后为`None`。
+    pub(crate) meta: Option<MetaCell>,
     // TODO 2026-01-10 01:27 增加特征描述和后续操作的优化
 }
 
+/// 数据的来源信息。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ItemMeta {
+    /// 来源文件。
+    pub(crate) file: Rc<String>,
+    /// 在来源文件中的行号，从1开始。
+    pub(crate) line: usize,
+    /// 结构化数据源解析出的各个字段，例如`:csv`的各列。
+    pub(crate) fields: Option<ItemFields>,
+}
+
+/// 数据的各个字段。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct ItemFields {
+    /// 字段名称，例如CSV的表头，同一数据源的所有数据共享。
+    pub(crate) names: Option<Rc<Vec<String>>>,
+    pub(crate) values: Vec<String>,
+}
+
+/// 数据源产生每个数据时写入该数据的来源信息，下游的惰性操作在处理同一数据时读取。
+/// 仅当数据源与读取者之间的操作都逐个拉取、不缓存也不产生新数据时才正确，其他操作需要将`Pipe::meta`置为`None`。
+pub(crate) type MetaCell = Rc<RefCell<Option<ItemMeta>>>;
+
+/// 使用当前数据及其来源信息格式化：`{v}`为数据本身，`{file}`和`{line}`为来源文件和行号，没有来源信息时均为空字符串；
+/// 数据有字段时，可以按照位置`{0}`或者字段名称`{name}`引用，与上述参数同名的字段只能按照位置引用。
+pub(crate) fn fmt_with_meta(fmt: &str, item: &str, meta: Option<&MetaCell>) -> Result<String, RpErr> {
+    let guard = meta.map(|cell| cell.borrow());
+    let meta = guard.as_ref().and_then(|meta| meta.as_ref());
+    let (file, line) = match meta {
+        Some(meta) => (FmtArg::from(&*meta.file), FmtArg::from(meta.line as Integer)),
+        None => (FmtArg::from(""), FmtArg::from("")),
+    };
+    let mut named = vec![("v", FmtArg::from(item)), ("file", file), ("line", line)];
+    let fields = meta.and_then(|meta| meta.fields.as_ref());
+    if let Some(ItemFields { names: Some(names), values }) = fields {
+        named.extend(names.iter().zip(values).map(|(name, value)| (name.as_str(), FmtArg::from(value))));
+    }
+    let positional = fields.map_or(vec![], |fields| fields.values.iter().map(FmtArg::from).collect());
+    fmt_positional_args(fmt, &positional, &named)
+}
+
 impl Iterator for Pipe {
     type Item = String;
 
@@ -13,14 +62,17 @@ impl Iterator for Pipe {
 
 impl Pipe {
     pub(crate) fn op_map(self, f: impl FnMut(String) -> String + 'static) -> Pipe {
-        Pipe { iter: Box::new(self.map(f)) }
+        let meta = self.meta.clone();
+        Pipe { iter: Box::new(self.map(f)), meta }
     }
 
     pub(crate) fn op_filter(self, f: impl FnMut(&String) -> bool + 'static) -> Pipe {
-        Pipe { iter: Box::new(self.filter(f)) }
+        let meta = self.meta.clone();
+        Pipe { iter: Box::new(self.filter(f)), meta }
     }
 
     pub(crate) fn op_inspect(self, f: impl FnMut(&String) + 'static) -> Pipe {
-        Pipe { iter: Box::new(self.inspect(f)) }
+        let meta = self.meta.clone();
+        Pipe { iter: Box::new(self.inspect(f)), meta }
     }
 }
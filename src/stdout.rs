@@ -0,0 +1,56 @@
+use crate::err::RpErr;
+use std::cell::RefCell;
+use std::io::{BufWriter, ErrorKind, Stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+thread_local! {
+    /// 写入标准输出的缓冲区，同一线程中`:peek`与输出共享，保证两者的数据顺序一致。
+    /// `:tee`的输出在单独的线程中执行，使用各自的缓冲区，结束时需要调用`flush`。
+    static STDOUT: RefCell<BufWriter<Stdout>> = RefCell::new(BufWriter::new(std::io::stdout()));
+}
+
+/// 是否每个数据之后都刷新缓冲区，输入逐渐产生数据（例如跟踪文件）时开启，避免数据长时间停留在缓冲区中。
+static LINE_FLUSH: AtomicBool = AtomicBool::new(false);
+
+/// 设置是否每个数据之后都刷新缓冲区。
+pub(crate) fn set_line_flush(line_flush: bool) {
+    LINE_FLUSH.store(line_flush, Ordering::Relaxed);
+}
+
+/// 输出一行数据。数据先写入缓冲区，缓冲区写满、按行刷新或程序结束时才真正输出。
+/// 下游已关闭（例如`rp ... | head`）时返回`RpErr::StdOutClosed`，由调用方停止流水线并正常退出。
+pub(crate) fn write_line(item: &str) -> Result<(), RpErr> {
+    write_item(item, "\n")
+}
+
+/// 输出一个数据及其结束符，缓冲及错误规则同`write_line`。
+pub(crate) fn write_item(item: &str, ending: &str) -> Result<(), RpErr> {
+    STDOUT
+        .with_borrow_mut(|writer| {
+            writer.write_all(item.as_bytes())?;
+            writer.write_all(ending.as_bytes())?;
+            if LINE_FLUSH.load(Ordering::Relaxed) { writer.flush() } else { Ok(()) }
+        })
+        .map_err(handle_err)
+}
+
+/// 刷新当前线程的缓冲区，错误规则同`write_line`。
+pub(crate) fn flush() -> Result<(), RpErr> {
+    STDOUT.with_borrow_mut(|writer| writer.flush()).map_err(handle_err)
+}
+
+fn handle_err(err: std::io::Error) -> RpErr {
+    if err.kind() == ErrorKind::BrokenPipe { RpErr::StdOutClosed } else { RpErr::WriteToStdOutErr(err.to_string()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_err() {
+        let err = handle_err(std::io::Error::new(ErrorKind::StorageFull, "no space left on device"));
+        assert_eq!(RpErr::WriteToStdOutErr("no space left on device".to_string()), err);
+        assert_eq!(RpErr::StdOutClosed, handle_err(std::io::Error::from(ErrorKind::BrokenPipe)));
+    }
+}